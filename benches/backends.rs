@@ -0,0 +1,37 @@
+//! Compares the cost of creating and checking a timed-option per backend.
+//!
+//! Run with `cargo bench --features quanta`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use timed_option::{TimedOption, TtlBackend};
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn bench<B>(name: &str)
+where
+    B: TtlBackend<Duration = Duration>,
+{
+    let ttl = Duration::from_secs(3500);
+
+    // warm up the clock, quanta calibrates on first use
+    black_box(TimedOption::<u64, B>::new(0, ttl).is_some());
+
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        let token = TimedOption::<_, B>::new(black_box(i), ttl);
+        black_box(token.is_some());
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{name:>16}: {:>6.1} ns/iter",
+        elapsed.as_nanos() as f64 / f64::from(ITERATIONS)
+    );
+}
+
+fn main() {
+    bench::<std::time::Instant>("std::Instant");
+    bench::<quanta::Instant>("quanta::Instant");
+}
@@ -0,0 +1,111 @@
+//! Serializes a [`TimedOption`] through a [`SerializableDeadline`], one wire
+//! format for every backend whose duration is [`DurationStd`], e.g.
+//! [`Instant`](std::time::Instant), [`SystemTime`](std::time::SystemTime) and
+//! `chrono`.
+//!
+//! The wire format is `{ "value": .., "deadline": .. }` where `deadline` is
+//! either `{ "Remaining": { "secs": .., "nanos": .. } }` or `"Expired"`,
+//! deserializing re-stamps the deadline as `now() + remaining`.
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! use std::time::{Instant, SystemTime};
+//! use timed_option::TimedOption;
+//!
+//! # #[cfg(feature = "std")]
+//! #[derive(Serialize, Deserialize)]
+//! struct Cache {
+//!     #[serde(with = "timed_option::as_deadline")]
+//!     session: TimedOption<String, Instant>,
+//!     #[serde(with = "timed_option::as_deadline")]
+//!     token: TimedOption<String, SystemTime>,
+//! }
+//! ```
+
+use core::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{DurationStd, TimedOption, TtlBackend};
+
+/// A backend agnostic deadline, relative to when it was created.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SerializableDeadline {
+    /// Time left until the deadline.
+    Remaining(Duration),
+    /// The deadline has been reached.
+    Expired,
+}
+
+impl SerializableDeadline {
+    /// Captures the time left until `deadline`.
+    #[inline]
+    pub fn new<B>(deadline: &B) -> Self
+    where
+        B: TtlBackend,
+        B::Duration: DurationStd,
+    {
+        match deadline.remaining() {
+            Some(remaining) => SerializableDeadline::Remaining(remaining.to_std()),
+            None => SerializableDeadline::Expired,
+        }
+    }
+
+    /// Re-stamps the deadline as `now() + remaining`.
+    #[inline]
+    pub fn into_backend<B>(self) -> B
+    where
+        B: TtlBackend,
+        B::Duration: DurationStd,
+    {
+        match self {
+            SerializableDeadline::Remaining(remaining) if !remaining.is_zero() => {
+                B::now().add(B::Duration::from_std(remaining))
+            }
+            _ => B::expired(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DeadlineRef<'a, T> {
+    value: &'a Option<T>,
+    deadline: SerializableDeadline,
+}
+
+#[derive(Deserialize)]
+struct Deadline<T> {
+    value: Option<T>,
+    deadline: SerializableDeadline,
+}
+
+/// Serializes the value with its [`SerializableDeadline`].
+pub fn serialize<T, B, S>(option: &TimedOption<T, B>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    B: TtlBackend,
+    B::Duration: DurationStd,
+    S: Serializer,
+{
+    DeadlineRef {
+        value: &option.value,
+        deadline: SerializableDeadline::new(&option.ttl),
+    }
+    .serialize(serializer)
+}
+
+/// Deserializes the value, re-stamping the ttl from its
+/// [`SerializableDeadline`].
+pub fn deserialize<'de, T, B, D>(deserializer: D) -> Result<TimedOption<T, B>, D::Error>
+where
+    T: Deserialize<'de>,
+    B: TtlBackend,
+    B::Duration: DurationStd,
+    D: Deserializer<'de>,
+{
+    let Deadline { value, deadline } = Deadline::deserialize(deserializer)?;
+    Ok(TimedOption {
+        value,
+        ttl: deadline.into_backend(),
+    })
+}
@@ -0,0 +1,70 @@
+//! Serializes a [`TimedOption`] as its remaining time-to-live instead of its
+//! raw deadline, which is meaningless outside of the process for monotonic
+//! clocks like [`Instant`](std::time::Instant).
+//!
+//! The wire format is `{ "value": .., "remaining_secs": .. }`, deserializing
+//! re-stamps the deadline as `now() + remaining_secs`. An expired ttl is
+//! written as `0.0` and read back as expired.
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! use std::time::Instant;
+//! use timed_option::TimedOption;
+//!
+//! # #[cfg(feature = "std")]
+//! #[derive(Serialize, Deserialize)]
+//! struct Cache {
+//!     #[serde(with = "timed_option::as_remaining")]
+//!     token: TimedOption<String, Instant>,
+//! }
+//! ```
+
+use core::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{TimedOption, TtlBackend};
+
+#[derive(Serialize)]
+struct RemainingRef<'a, T> {
+    value: &'a Option<T>,
+    remaining_secs: f64,
+}
+
+#[derive(Deserialize)]
+struct Remaining<T> {
+    value: Option<T>,
+    remaining_secs: f64,
+}
+
+/// Serializes the value with the remaining ttl in seconds.
+pub fn serialize<T, B, S>(option: &TimedOption<T, B>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    B: TtlBackend<Duration = Duration>,
+    S: Serializer,
+{
+    RemainingRef {
+        value: &option.value,
+        remaining_secs: option.ttl.remaining().map_or(0.0, |dt| dt.as_secs_f64()),
+    }
+    .serialize(serializer)
+}
+
+/// Deserializes the value, re-stamping the ttl from the remaining seconds.
+pub fn deserialize<'de, T, B, D>(deserializer: D) -> Result<TimedOption<T, B>, D::Error>
+where
+    T: Deserialize<'de>,
+    B: TtlBackend<Duration = Duration>,
+    D: Deserializer<'de>,
+{
+    let Remaining {
+        value,
+        remaining_secs,
+    } = Remaining::deserialize(deserializer)?;
+    let ttl = match remaining_secs > 0.0 {
+        true => B::now().add(Duration::try_from_secs_f64(remaining_secs).unwrap_or(Duration::MAX)),
+        false => B::expired(),
+    };
+    Ok(TimedOption { value, ttl })
+}
@@ -0,0 +1,71 @@
+//! Serializes a [`TimedOption`] as its remaining time-to-live in whole
+//! milliseconds, for any backend whose duration is [`DurationMillis`]. This
+//! is the portable format to keep entries across restarts.
+//!
+//! The wire format is `{ "value": .., "remaining": .. }` where `remaining` is
+//! `null` once the ttl has expired or when there is no value, deserializing
+//! re-stamps the deadline as `now() + remaining`. Less than a millisecond
+//! left is read back as expired.
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! # #[cfg(feature = "std")]
+//! use timed_option::{EpochMillis, TimedOption};
+//!
+//! # #[cfg(feature = "std")]
+//! #[derive(Serialize, Deserialize)]
+//! struct Cache {
+//!     #[serde(with = "timed_option::as_remaining_millis")]
+//!     token: TimedOption<String, EpochMillis>,
+//! }
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{DurationMillis, TimedOption, TtlBackend};
+
+#[derive(Serialize)]
+struct RemainingRef<'a, T> {
+    value: &'a Option<T>,
+    remaining: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Remaining<T> {
+    value: Option<T>,
+    remaining: Option<u64>,
+}
+
+/// Serializes the value with the remaining ttl in milliseconds.
+pub fn serialize<T, B, S>(option: &TimedOption<T, B>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    B: TtlBackend,
+    B::Duration: DurationMillis,
+    S: Serializer,
+{
+    RemainingRef {
+        value: &option.value,
+        remaining: match option.value {
+            Some(_) => option.ttl.remaining().map(|dt| dt.to_millis()),
+            None => None,
+        },
+    }
+    .serialize(serializer)
+}
+
+/// Deserializes the value, re-stamping the ttl from the remaining milliseconds.
+pub fn deserialize<'de, T, B, D>(deserializer: D) -> Result<TimedOption<T, B>, D::Error>
+where
+    T: Deserialize<'de>,
+    B: TtlBackend,
+    B::Duration: DurationMillis,
+    D: Deserializer<'de>,
+{
+    let Remaining { value, remaining } = Remaining::deserialize(deserializer)?;
+    let ttl = match remaining {
+        Some(millis) if millis > 0 => B::now().add(B::Duration::from_millis(millis)),
+        _ => B::expired(),
+    };
+    Ok(TimedOption { value, ttl })
+}
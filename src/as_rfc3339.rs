@@ -0,0 +1,63 @@
+//! Serializes a [`TimedOption`] on the `chrono` backend with its absolute
+//! deadline as an RFC 3339 timestamp.
+//!
+//! The wire format is `{ "value": .., "expires_at": "2025-01-01T00:00:00Z" }`,
+//! deserializing restores the exact deadline without re-stamping it.
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! use chrono::{DateTime, Utc};
+//! use timed_option::TimedOption;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Session {
+//!     #[serde(with = "timed_option::as_rfc3339")]
+//!     token: TimedOption<String, DateTime<Utc>>,
+//! }
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::TimedOption;
+
+#[derive(Serialize)]
+struct DeadlineRef<'a, T> {
+    value: &'a Option<T>,
+    expires_at: &'a DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct Deadline<T> {
+    value: Option<T>,
+    expires_at: DateTime<Utc>,
+}
+
+/// Serializes the value with the deadline as an RFC 3339 timestamp.
+pub fn serialize<T, S>(
+    option: &TimedOption<T, DateTime<Utc>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    DeadlineRef {
+        value: &option.value,
+        expires_at: &option.ttl,
+    }
+    .serialize(serializer)
+}
+
+/// Deserializes the value and its RFC 3339 deadline as is.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<TimedOption<T, DateTime<Utc>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let Deadline { value, expires_at } = Deadline::deserialize(deserializer)?;
+    Ok(TimedOption {
+        value,
+        ttl: expires_at,
+    })
+}
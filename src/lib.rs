@@ -1,4 +1,22 @@
-#![doc = include_str!("../README.md")]
+#![cfg_attr(feature = "std", doc = include_str!("../README.md"))]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "serde")]
+pub mod as_deadline;
+#[cfg(feature = "serde")]
+pub mod as_remaining;
+#[cfg(feature = "serde")]
+pub mod as_remaining_millis;
+#[cfg(all(feature = "serde", feature = "chrono"))]
+pub mod as_rfc3339;
+#[cfg(feature = "serde")]
+pub mod skip_expired;
+
+#[cfg(feature = "serde")]
+pub use as_deadline::SerializableDeadline;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Timed Option
@@ -16,7 +34,8 @@ impl<T, B> TimedOption<T, B>
 where
     B: TtlBackend,
 {
-    /// Some value of type `T` with a ttl.
+    /// Some value of type `T` with a ttl. The value expires once the ttl has
+    /// fully elapsed, so a zero ttl gives an already expired value.
     #[inline]
     pub fn new(value: T, ttl: B::Duration) -> Self {
         TimedOption {
@@ -25,6 +44,31 @@ where
         }
     }
 
+    /// Like [`new`](Self::new), but `None` if `now() + ttl` can not be
+    /// represented by the backend instead of saturating.
+    #[inline]
+    pub fn checked_new(value: T, ttl: B::Duration) -> Option<Self> {
+        Some(TimedOption {
+            value: Some(value),
+            ttl: B::now().checked_add(ttl)?,
+        })
+    }
+
+    /// Some values of type `T` that all share one deadline, `now()` is read
+    /// a single time for the whole batch.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn new_batch<I>(values: I, ttl: B::Duration) -> alloc::vec::Vec<Self>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let deadline = B::now().add(ttl);
+        values
+            .into_iter()
+            .map(|value| TimedOption::with_deadline(value, deadline.clone()))
+            .collect()
+    }
+
     /// No value with a expired ttl
     #[inline]
     pub fn empty() -> Self {
@@ -34,10 +78,65 @@ where
         }
     }
 
+    /// Some value of type `T` valid until the absolute `deadline`.
+    #[inline]
+    pub fn with_deadline(value: T, deadline: B) -> Self {
+        TimedOption {
+            value: Some(value),
+            ttl: deadline,
+        }
+    }
+
+    /// Some value of type `T` with a ttl shifted by a random offset, so
+    /// values created together do not all expire at once.
+    ///
+    /// The offset is sampled uniformly from `-jitter..=jitter`, a ttl that
+    /// would end up negative is clamped to zero.
+    #[cfg(feature = "rand")]
+    #[inline]
+    pub fn new_jittered(value: T, ttl: B::Duration, jitter: B::Duration) -> Self
+    where
+        B: TtlBackend<Duration = core::time::Duration>,
+    {
+        TimedOption::new(value, jittered(ttl, jitter))
+    }
+
+    /// Returns a [`TimedOptionBuilder`].
+    #[inline]
+    pub fn builder() -> TimedOptionBuilder<T, B> {
+        TimedOptionBuilder::new()
+    }
+
+    /// Some value of type `T` with a expired ttl.
+    #[inline]
+    pub fn new_expired(value: T) -> Self {
+        TimedOption {
+            value: Some(value),
+            ttl: B::expired(),
+        }
+    }
+
+    /// Builds a timed-option from a value and a raw ttl deadline, the deadline
+    /// is stored as is. This is the inverse of [`into_parts`](Self::into_parts).
+    #[inline]
+    pub fn from_parts(value: Option<T>, deadline: B) -> Self {
+        TimedOption {
+            value,
+            ttl: deadline,
+        }
+    }
+
     ////////////////////////////////////////////////////////////////////////////
     // consumption + as_refs
     ////////////////////////////////////////////////////////////////////////////
 
+    /// Returns the raw value and ttl deadline, regardless of expiry. This is
+    /// the inverse of [`from_parts`](Self::from_parts).
+    #[inline]
+    pub fn into_parts(self) -> (Option<T>, B) {
+        (self.value, self.ttl)
+    }
+
     /// Returns an `Option<T>`. If the value is some but expired a `None` is returned.
     #[inline]
     pub fn into_option(self) -> Option<T> {
@@ -53,6 +152,100 @@ where
         self.as_ref().into_option()
     }
 
+    /// Returns the value or `default`. An expired value is treated as `None`,
+    /// so `default` is returned for it.
+    #[inline]
+    pub fn unwrap_or(self, default: T) -> T {
+        self.into_option().unwrap_or(default)
+    }
+
+    /// Returns the value or computes it from `f`. An expired value is treated
+    /// as `None`, so `f` is called for it.
+    #[inline]
+    pub fn unwrap_or_else<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        self.into_option().unwrap_or_else(f)
+    }
+
+    /// Returns the value or `T::default()`. An expired value is treated as
+    /// `None`, so the default is returned for it.
+    #[inline]
+    pub fn unwrap_or_default(self) -> T
+    where
+        T: Default,
+    {
+        self.into_option().unwrap_or_default()
+    }
+
+    /// Transforms the timed-option into a `Result<T, E>`, mapping a valid value
+    /// to `Ok` and anything else to `Err(err)`. Note that an expired value
+    /// gives `Err` too.
+    #[inline]
+    pub fn ok_or<E>(self, err: E) -> Result<T, E> {
+        self.into_option().ok_or(err)
+    }
+
+    /// Transforms the timed-option into a `Result<T, E>`, mapping a valid value
+    /// to `Ok` and anything else to `Err(f())`. Note that an expired value
+    /// gives `Err` too.
+    #[inline]
+    pub fn ok_or_else<E, F>(self, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> E,
+    {
+        self.into_option().ok_or_else(f)
+    }
+
+    /// Returns the value if it is valid, otherwise a [`TimedError`] telling
+    /// whether it has expired or is absent.
+    #[inline]
+    pub fn into_result(self) -> Result<T, TimedError> {
+        match self.into_timed_value() {
+            TimedValue::Valid(value) => Ok(value),
+            TimedValue::Expired(_) => Err(TimedError::Expired),
+            TimedValue::None => Err(TimedError::Absent),
+        }
+    }
+
+    /// Returns a reference to the value if it is valid, otherwise a
+    /// [`TimedError`] telling whether it has expired or is absent.
+    #[inline]
+    pub fn as_result(&self) -> Result<&T, TimedError> {
+        self.as_ref().into_result()
+    }
+
+    /// Returns the value if it is valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics with `msg` followed by `: value expired` or `: value absent`
+    /// if the value has expired or is `None`.
+    #[inline]
+    #[track_caller]
+    pub fn expect_valid(self, msg: &str) -> T {
+        match self.into_timed_value() {
+            TimedValue::Valid(value) => value,
+            TimedValue::Expired(_) => panic!("{msg}: value expired"),
+            TimedValue::None => panic!("{msg}: value absent"),
+        }
+    }
+
+    /// Returns the value, expired or not.
+    ///
+    /// # Panics
+    ///
+    /// Panics with `msg` followed by `: value absent` if the value is `None`.
+    #[inline]
+    #[track_caller]
+    pub fn expect_some(self, msg: &str) -> T {
+        match self.value {
+            Some(value) => value,
+            None => panic!("{msg}: value absent"),
+        }
+    }
+
     /// Returns an `TimedValue<T>`.
     #[inline]
     pub fn into_timed_value(self) -> TimedValue<T> {
@@ -69,6 +262,17 @@ where
         self.as_ref().into_timed_value()
     }
 
+    /// Returns an `TimedValue<&mut T>`, so the value can be changed while
+    /// knowing whether it is still valid.
+    #[inline]
+    pub fn as_timed_value_mut(&mut self) -> TimedValue<&mut T> {
+        match (self.value.as_mut(), self.ttl.is_valid()) {
+            (Some(value), true) => TimedValue::Valid(value),
+            (Some(value), false) => TimedValue::Expired(value),
+            (None, _) => TimedValue::None,
+        }
+    }
+
     /// Converts from `&TimedOption<T>` to `TimedOption<&T>`.
     #[inline]
     pub fn as_ref(&self) -> TimedOption<&T, B> {
@@ -78,224 +282,3112 @@ where
         }
     }
 
-    ////////////////////////////////////////////////////////////////////////////
-    // mutations
-    ////////////////////////////////////////////////////////////////////////////
+    /// Returns an `Option<&mut T>`. If the value is some but expired a `None`
+    /// is returned.
+    #[inline]
+    pub fn as_mut(&mut self) -> Option<&mut T> {
+        match self.ttl.is_valid() {
+            true => self.value.as_mut(),
+            false => None,
+        }
+    }
 
-    /// Expires the current ttl.
+    /// Returns an `Option<&T>` of the stored value, even if it has expired.
+    /// Only `None` if there is no value at all.
     #[inline]
-    pub fn expire(&mut self) {
-        self.ttl = B::expired();
+    pub fn peek(&self) -> Option<&T> {
+        self.value.as_ref()
     }
 
-    /// Sets value to [`None`].
+    /// Returns an `Option<&mut T>` of the stored value, even if it has
+    /// expired. Only `None` if there is no value at all.
     #[inline]
-    pub fn clear(&mut self) {
-        self.value = None;
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.value.as_mut()
     }
 
-    /// Takes the value out of the [`TimedOption`], returning an [`Option`] and
-    /// leaving a [`None`] in its place.
+    /// Returns the stored value together with whether it is still valid,
+    /// `None` only if there is no value. Handy to serve stale content while
+    /// it is being refreshed.
     #[inline]
-    pub fn take(&mut self) -> Option<T> {
-        let value = self.value.take();
-        match self.ttl.is_valid() {
-            true => value,
-            false => None,
-        }
+    pub fn get_stale(&self) -> Option<(&T, bool)> {
+        self.peek().map(|value| (value, self.ttl.is_valid()))
     }
 
-    /// Takes the value out of the [`TimedOption`], Returning a [`TimedValue`]
-    /// and leaving a [`None`] in its place.
+    /// Returns an iterator over the value, yielding nothing if it is `None`
+    /// or expired.
     #[inline]
-    pub fn take_timed_value(&mut self) -> TimedValue<T> {
-        match (self.value.take(), self.ttl.is_valid()) {
-            (Some(value), true) => TimedValue::Valid(value),
-            (Some(value), false) => TimedValue::Expired(value),
-            (None, _) => TimedValue::None,
-        }
+    pub fn iter(&self) -> core::option::IntoIter<&T> {
+        self.as_option().into_iter()
     }
 
     ////////////////////////////////////////////////////////////////////////////
-    // utility functions
+    // combinators
     ////////////////////////////////////////////////////////////////////////////
 
-    /// Returns `true` if the timed-option is `Some` value and has not expired.
+    /// Maps a `TimedOption<T>` to a `TimedOption<U>` by applying a function to
+    /// the contained value, expired or not. The ttl is carried over unchanged.
+    ///
+    /// Use `as_ref().map(..)` to map without consuming the timed-option.
     #[inline]
-    pub fn is_some(&self) -> bool {
-        self.value.is_some() & self.ttl.is_valid()
+    pub fn map<U, F>(self, f: F) -> TimedOption<U, B>
+    where
+        F: FnOnce(T) -> U,
+    {
+        TimedOption {
+            value: self.value.map(f),
+            ttl: self.ttl,
+        }
     }
 
-    /// Returns `true` if the timed-option is `None` value or it has expired.
+    /// Like [`map`](Self::map) for a fallible `f`, the ttl is carried over on
+    /// success and the error is returned otherwise. A `None` value gives an
+    /// empty timed-option without calling `f`.
     #[inline]
-    pub fn is_none(&self) -> bool {
-        self.value.is_none() | self.ttl.is_expired()
+    pub fn try_map<U, E, F>(self, f: F) -> Result<TimedOption<U, B>, E>
+    where
+        F: FnOnce(T) -> Result<U, E>,
+    {
+        match self.value {
+            Some(value) => Ok(TimedOption {
+                value: Some(f(value)?),
+                ttl: self.ttl,
+            }),
+            None => Ok(TimedOption::empty()),
+        }
     }
-}
 
-////////////////////////////////////////////////////////////////////////////////
-// Timed Value
-////////////////////////////////////////////////////////////////////////////////
+    /// Maps the deadline with `f`, keeping the value. Moving the deadline
+    /// earlier can expire a currently valid value.
+    #[inline]
+    pub fn map_ttl<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(B) -> B,
+    {
+        self.ttl = f(self.ttl);
+        self
+    }
 
-/// An enum representing a value that is associated with a time validity status.
-///
-/// `TimedValue` can be used to indicate whether a value is valid, expired, or absent (none).
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub enum TimedValue<T> {
-    /// value of type `T` that is considered valid
-    Valid(T),
-    /// value of type `T` that has expired and is no longer considered valid
-    Expired(T),
-    /// Indicates the absence of a value.
-    None,
-}
+    /// Maps the deadline to another backend with `f`, keeping the value.
+    ///
+    /// `f` is responsible for producing a sensible deadline, e.g. by adding
+    /// the [remaining](TtlBackend::remaining) time to the new backend's now.
+    #[inline]
+    pub fn convert_backend<C, F>(self, f: F) -> TimedOption<T, C>
+    where
+        C: TtlBackend,
+        F: FnOnce(B) -> C,
+    {
+        TimedOption {
+            value: self.value,
+            ttl: f(self.ttl),
+        }
+    }
 
-impl<T> TimedValue<T> {
-    /// Returns `true` if the [`TimedValue`]` is `Valid`.
+    /// Returns an empty timed-option if the value is `None` or expired,
+    /// otherwise calls `f` with the value and returns the result.
+    ///
+    /// The ttl of the returned timed-option is the one produced by `f`, the
+    /// original ttl is discarded.
     #[inline]
-    pub const fn is_valid(&self) -> bool {
-        match self {
-            TimedValue::Valid(_) => true,
-            TimedValue::Expired(_) => false,
-            TimedValue::None => false,
+    pub fn and_then<U, F>(self, f: F) -> TimedOption<U, B>
+    where
+        F: FnOnce(T) -> TimedOption<U, B>,
+    {
+        match self.into_option() {
+            Some(value) => f(value),
+            None => TimedOption::empty(),
         }
     }
 
-    /// Returns `true` if the TimedValue is `Expired`.
+    /// Clears the value if it is valid and `predicate` returns `false`, the ttl
+    /// is kept as is. The predicate is not called for `None` or expired values.
     #[inline]
-    pub const fn is_expired(&self) -> bool {
-        match self {
-            TimedValue::Valid(_) => false,
-            TimedValue::Expired(_) => true,
-            TimedValue::None => false,
+    pub fn filter<P>(mut self, predicate: P) -> Self
+    where
+        P: FnOnce(&T) -> bool,
+    {
+        if let Some(value) = self.as_option() {
+            if !predicate(value) {
+                self.value = None;
+            }
         }
+        self
     }
 
-    /// Returns `true` if the TimedValue is `None`.
+    /// Returns `other` if the value is valid, otherwise returns an empty
+    /// timed-option. An expired value counts as `None` here.
     #[inline]
-    pub const fn is_none(&self) -> bool {
-        match self {
-            TimedValue::Valid(_) => false,
-            TimedValue::Expired(_) => false,
-            TimedValue::None => true,
+    pub fn and<U>(self, other: TimedOption<U, B>) -> TimedOption<U, B> {
+        match self.is_some() {
+            true => other,
+            false => TimedOption::empty(),
         }
     }
 
-    /// Returns `true` if the TimedValue is `Valid` or `Expired`.
+    /// Returns `self` if the value is valid, otherwise returns `other`. An
+    /// expired value counts as `None` here.
     #[inline]
-    pub const fn has_value(&self) -> bool {
-        match self {
-            TimedValue::Valid(_) => true,
-            TimedValue::Expired(_) => true,
-            TimedValue::None => false,
+    pub fn or(self, other: Self) -> Self {
+        match self.is_some() {
+            true => self,
+            false => other,
         }
     }
 
-    /// Converts from `&TimedValue<T>` to `TimedValue<&T>`.
+    /// Returns `self` if the value is valid, otherwise calls `f` and returns
+    /// the result. An expired value counts as `None` here.
     #[inline]
-    pub const fn as_ref(&self) -> TimedValue<&T> {
-        match *self {
-            TimedValue::Valid(ref inner) => TimedValue::Valid(inner),
-            TimedValue::Expired(ref inner) => TimedValue::Expired(inner),
-            TimedValue::None => TimedValue::None,
+    pub fn or_else<F>(self, f: F) -> Self
+    where
+        F: FnOnce() -> Self,
+    {
+        match self.is_some() {
+            true => self,
+            false => f(),
         }
     }
-}
 
-////////////////////////////////////////////////////////////////////////////////
-// Conversion
-////////////////////////////////////////////////////////////////////////////////
+    /// Returns whichever of `self` and `other` is valid if exactly one of them
+    /// is, otherwise returns an empty timed-option.
+    #[inline]
+    pub fn xor(self, other: Self) -> Self {
+        match (self.is_some(), other.is_some()) {
+            (true, false) => self,
+            (false, true) => other,
+            _ => TimedOption::empty(),
+        }
+    }
 
-impl<T, B> From<TimedOption<T, B>> for Option<T>
-where
-    B: TtlBackend,
-{
+    /// Returns whichever of `self` and `other` has the later deadline, with or
+    /// without a value and expired or not, preferring `self` on a tie. A
+    /// cleared timed-option keeps its deadline, so a later clear wins over an
+    /// earlier write.
     #[inline]
-    fn from(value: TimedOption<T, B>) -> Self {
-        value.into_option()
+    pub fn merge(self, other: Self) -> Self
+    where
+        B: Ord,
+    {
+        match other.ttl > self.ttl {
+            true => other,
+            false => self,
+        }
     }
-}
 
-impl<T, B> From<TimedOption<T, B>> for TimedValue<T>
-where
-    B: TtlBackend,
-{
+    /// Zips `self` with `other` if both values are valid, otherwise returns an
+    /// empty timed-option.
+    ///
+    /// The pair gets the earlier of the two ttls, so it expires as soon as
+    /// either of its parts would.
     #[inline]
-    fn from(value: TimedOption<T, B>) -> Self {
-        value.into_timed_value()
+    pub fn zip<U>(self, other: TimedOption<U, B>) -> TimedOption<(T, U), B>
+    where
+        B: Ord,
+    {
+        match self.is_some() && other.is_some() {
+            true => TimedOption {
+                value: self.value.zip(other.value),
+                ttl: core::cmp::min(self.ttl, other.ttl),
+            },
+            false => TimedOption::empty(),
+        }
     }
-}
 
-////////////////////////////////////////////////////////////////////////////////
-// TTL Backent
-////////////////////////////////////////////////////////////////////////////////
+    /// Calls `f` with a reference to the value if it is valid, then returns
+    /// the timed-option unchanged.
+    #[inline]
+    pub fn inspect<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&T),
+    {
+        if let Some(value) = self.as_option() {
+            f(value);
+        }
+        self
+    }
 
-pub trait TtlBackend: Clone {
-    type Duration;
+    /// Calls `f` with a reference to the value if it has expired, then returns
+    /// the timed-option unchanged.
+    #[inline]
+    pub fn inspect_expired<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&T),
+    {
+        if let TimedValue::Expired(value) = self.as_timed_value() {
+            f(value);
+        }
+        self
+    }
 
-    fn now() -> Self;
-    fn expired() -> Self;
-    fn add(self, dt: Self::Duration) -> Self;
-    fn is_valid(&self) -> bool;
-    fn is_expired(&self) -> bool;
-}
+    ////////////////////////////////////////////////////////////////////////////
+    // mutations
+    ////////////////////////////////////////////////////////////////////////////
 
-impl TtlBackend for std::time::Instant {
-    type Duration = std::time::Duration;
+    /// Expires the current ttl.
+    #[inline]
+    pub fn expire(&mut self) {
+        self.ttl = B::expired();
+    }
 
+    /// Renews the ttl without touching the value, an expired value becomes
+    /// valid again. Does nothing if the value is [`None`].
     #[inline]
-    fn now() -> Self {
-        std::time::Instant::now()
+    pub fn refresh(&mut self, ttl: B::Duration) {
+        if self.value.is_some() {
+            self.ttl = B::now().add(ttl);
+        }
     }
 
+    /// Pushes the current ttl out by `by`. Unlike [`refresh`](Self::refresh)
+    /// the new ttl is relative to the old one, not to now, even if it has
+    /// already expired.
     #[inline]
-    fn expired() -> Self {
-        std::time::Instant::now()
+    pub fn extend(&mut self, by: B::Duration) {
+        self.ttl = self.ttl.clone().add(by);
     }
 
+    /// Sets value to [`None`].
     #[inline]
-    fn add(self, dt: Self::Duration) -> Self {
-        self + dt
+    pub fn clear(&mut self) {
+        self.value = None;
     }
 
+    /// Sets the value to [`None`] if it has expired, returning `true` if a
+    /// value was dropped. Valid values are left untouched.
     #[inline]
-    fn is_valid(&self) -> bool {
-        *self > std::time::Instant::now()
+    pub fn clear_expired(&mut self) -> bool {
+        match self.value.is_some() && self.ttl.is_expired() {
+            true => {
+                self.value = None;
+                true
+            }
+            false => false,
+        }
     }
 
+    /// Sets the value to `value` with a new ttl, whatever the previous state.
     #[inline]
-    fn is_expired(&self) -> bool {
-        *self <= std::time::Instant::now()
+    pub fn set(&mut self, value: T, ttl: B::Duration) {
+        self.value = Some(value);
+        self.ttl = B::now().add(ttl);
     }
-}
 
-#[cfg(feature = "chrono")]
-impl TtlBackend for chrono::DateTime<chrono::Utc> {
-    type Duration = chrono::Duration;
+    /// Replaces the value with `value` and a new ttl, returning the old value
+    /// if it was valid.
+    #[inline]
+    pub fn replace(&mut self, value: T, ttl: B::Duration) -> Option<T> {
+        core::mem::replace(self, TimedOption::new(value, ttl)).into_option()
+    }
 
+    /// Replaces the value with `value` but keeps the deadline, returning the
+    /// old value expired or not. The new value inherits the old deadline, so
+    /// it is expired if the old one was.
     #[inline]
-    fn now() -> Self {
-        chrono::Utc::now()
+    pub fn replace_value(&mut self, value: T) -> Option<T> {
+        self.value.replace(value)
     }
 
+    /// Sets `value` with a new ttl only if the timed-option is `None` or
+    /// expired, returning `true` if it was replaced. A valid value is left
+    /// untouched and `value` is dropped.
     #[inline]
-    fn expired() -> Self {
-        chrono::Utc::now()
+    pub fn replace_if_expired(&mut self, value: T, ttl: B::Duration) -> bool {
+        match self.is_none() {
+            true => {
+                self.set(value, ttl);
+                true
+            }
+            false => false,
+        }
     }
 
+    /// Inserts `value` with a new ttl if the timed-option is `None` or expired,
+    /// then returns a mutable reference to the contained value.
     #[inline]
-    fn add(self, dt: Self::Duration) -> Self {
-        self + dt
+    pub fn get_or_insert(&mut self, value: T, ttl: B::Duration) -> &mut T {
+        self.get_or_insert_with(ttl, || value)
     }
 
+    /// Inserts the value computed from `f` with a new ttl if the timed-option
+    /// is `None` or expired, then returns a mutable reference to the contained
+    /// value. `f` is not called if the value is valid.
     #[inline]
-    fn is_valid(&self) -> bool {
-        *self > chrono::Utc::now()
+    pub fn get_or_insert_with<F>(&mut self, ttl: B::Duration, f: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        if self.is_none() {
+            let value = f();
+            self.ttl = B::now().add(ttl);
+            return self.value.insert(value);
+        }
+        match self.value {
+            Some(ref mut value) => value,
+            None => unreachable!(),
+        }
     }
 
+    /// Like [`get_or_insert_with`](Self::get_or_insert_with), also returning
+    /// the [`TimedState`] found before inserting, i.e. a fresh hit, a stale
+    /// hit or a miss.
     #[inline]
-    fn is_expired(&self) -> bool {
-        *self <= chrono::Utc::now()
+    pub fn get_or_insert_tracked<F>(&mut self, ttl: B::Duration, f: F) -> (&mut T, TimedState)
+    where
+        F: FnOnce() -> T,
+    {
+        let state = self.state();
+        if state != TimedState::Fresh {
+            let value = f();
+            self.ttl = B::now().add(ttl);
+            return (self.value.insert(value), state);
+        }
+        match self.value {
+            Some(ref mut value) => (value, state),
+            None => unreachable!(),
+        }
+    }
+
+    /// Like [`get_or_insert_with`](Self::get_or_insert_with) for a fallible
+    /// `f`. If `f` fails its error is returned and the timed-option is left
+    /// unchanged.
+    #[inline]
+    pub fn get_or_try_insert_with<E, F>(&mut self, ttl: B::Duration, f: F) -> Result<&mut T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        if self.is_none() {
+            let value = f()?;
+            self.ttl = B::now().add(ttl);
+            return Ok(self.value.insert(value));
+        }
+        match self.value {
+            Some(ref mut value) => Ok(value),
+            None => unreachable!(),
+        }
     }
+
+    /// Takes the value out of the [`TimedOption`], returning an [`Option`] and
+    /// leaving a [`None`] in its place.
+    #[inline]
+    pub fn take(&mut self) -> Option<T> {
+        let value = self.value.take();
+        match self.ttl.is_valid() {
+            true => value,
+            false => None,
+        }
+    }
+
+    /// Takes the value out of the [`TimedOption`] only if it has expired,
+    /// leaving a [`None`] in its place. A valid value is left untouched, this
+    /// is the mirror of [`take`](Self::take).
+    #[inline]
+    #[must_use]
+    pub fn take_expired(&mut self) -> Option<T> {
+        match self.ttl.is_expired() {
+            true => self.value.take(),
+            false => None,
+        }
+    }
+
+    /// Takes the value out of the [`TimedOption`] if it is valid and
+    /// `predicate` returns `true`, leaving a [`None`] in its place. The
+    /// predicate is not called for `None` or expired values.
+    #[inline]
+    pub fn take_if<P>(&mut self, predicate: P) -> Option<T>
+    where
+        P: FnOnce(&mut T) -> bool,
+    {
+        let take = match self.as_mut() {
+            Some(value) => predicate(value),
+            None => false,
+        };
+        match take {
+            true => self.value.take(),
+            false => None,
+        }
+    }
+
+    /// Takes the value out of the [`TimedOption`], Returning a [`TimedValue`]
+    /// and leaving a [`None`] in its place.
+    #[inline]
+    pub fn take_timed_value(&mut self) -> TimedValue<T> {
+        match (self.value.take(), self.ttl.is_valid()) {
+            (Some(value), true) => TimedValue::Valid(value),
+            (Some(value), false) => TimedValue::Expired(value),
+            (None, _) => TimedValue::None,
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // injected clocks
+    ////////////////////////////////////////////////////////////////////////////
+
+    /// Some value of type `T` with a ttl stamped from `clock` instead of
+    /// [`TtlBackend::now`].
+    #[inline]
+    pub fn new_with_clock<C>(clock: &C, value: T, ttl: B::Duration) -> Self
+    where
+        C: Clock<Instant = B>,
+    {
+        TimedOption {
+            value: Some(value),
+            ttl: clock.now().add(ttl),
+        }
+    }
+
+    /// Returns `true` if the value is `Some` and has not expired according to
+    /// `clock`.
+    #[inline]
+    pub fn is_some_with<C>(&self, clock: &C) -> bool
+    where
+        C: Clock<Instant = B>,
+    {
+        self.value.is_some() && self.ttl.is_valid_at(&clock.now())
+    }
+
+    /// Returns `true` if the value is `None` or has expired according to
+    /// `clock`.
+    #[inline]
+    pub fn is_none_with<C>(&self, clock: &C) -> bool
+    where
+        C: Clock<Instant = B>,
+    {
+        !self.is_some_with(clock)
+    }
+
+    /// Returns an `Option<T>`, checking expiry against `clock`.
+    #[inline]
+    pub fn into_option_with<C>(self, clock: &C) -> Option<T>
+    where
+        C: Clock<Instant = B>,
+    {
+        match self.ttl.is_valid_at(&clock.now()) {
+            true => self.value,
+            false => None,
+        }
+    }
+
+    /// Returns an `TimedValue<T>`, checking expiry against `clock`.
+    #[inline]
+    pub fn into_timed_value_with<C>(self, clock: &C) -> TimedValue<T>
+    where
+        C: Clock<Instant = B>,
+    {
+        match (self.value, self.ttl.is_valid_at(&clock.now())) {
+            (Some(value), true) => TimedValue::Valid(value),
+            (Some(value), false) => TimedValue::Expired(value),
+            (None, _) => TimedValue::None,
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // utility functions
+    ////////////////////////////////////////////////////////////////////////////
+
+    /// Returns `true` if the timed-option is `Some` value and has not expired.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.value.is_some() & self.ttl.is_valid()
+    }
+
+    /// Same as [`is_some`](Self::is_some), named after
+    /// [`TimedValue::is_valid`].
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.is_some()
+    }
+
+    /// Returns `true` if the ttl has not expired, whether there is a value or
+    /// not.
+    #[inline]
+    pub fn deadline_is_valid(&self) -> bool {
+        self.ttl.is_valid()
+    }
+
+    /// Returns the ttl deadline, also available when the value is `None`.
+    #[inline]
+    pub fn expires_at(&self) -> &B {
+        &self.ttl
+    }
+
+    /// Returns the time left before the value expires, or `None` if the value
+    /// is `None` or has expired.
+    #[inline]
+    pub fn remaining(&self) -> Option<B::Duration> {
+        match self.value {
+            Some(_) => self.ttl.remaining(),
+            None => None,
+        }
+    }
+
+    /// Returns `true` if the timed-option has a value that has expired. Unlike
+    /// [`is_none`](Self::is_none) this is `false` when there is no value.
+    #[inline]
+    pub fn is_expired(&self) -> bool {
+        self.value.is_some() & self.ttl.is_expired()
+    }
+
+    /// Returns the [`TimedState`] of the timed-option, reading the clock once
+    /// so the result is consistent.
+    #[inline]
+    pub fn state(&self) -> TimedState {
+        match (&self.value, self.ttl.is_valid()) {
+            (Some(_), true) => TimedState::Fresh,
+            (Some(_), false) => TimedState::Stale,
+            (None, _) => TimedState::Empty,
+        }
+    }
+
+    /// Returns `true` if the value is valid and equal to `x`.
+    #[inline]
+    pub fn contains<U>(&self, x: &U) -> bool
+    where
+        U: PartialEq<T>,
+    {
+        match self.as_option() {
+            Some(value) => x == value,
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the timed-option is `None` value or it has expired.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.value.is_none() | self.ttl.is_expired()
+    }
+}
+
+impl<T, B> TimedOption<&T, B>
+where
+    B: TtlBackend,
+{
+    /// Maps a `TimedOption<&T>` to a `TimedOption<T>` by cloning the value,
+    /// the ttl is carried over unchanged.
+    #[inline]
+    pub fn cloned(self) -> TimedOption<T, B>
+    where
+        T: Clone,
+    {
+        TimedOption {
+            value: self.value.cloned(),
+            ttl: self.ttl,
+        }
+    }
+
+    /// Maps a `TimedOption<&T>` to a `TimedOption<T>` by copying the value,
+    /// the ttl is carried over unchanged.
+    #[inline]
+    pub fn copied(self) -> TimedOption<T, B>
+    where
+        T: Copy,
+    {
+        TimedOption {
+            value: self.value.copied(),
+            ttl: self.ttl,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, B> TimedOption<alloc::borrow::Cow<'_, T>, B>
+where
+    T: alloc::borrow::ToOwned + ?Sized + 'static,
+    B: TtlBackend,
+{
+    /// Detaches a borrowed [`Cow`](alloc::borrow::Cow) value from its
+    /// lifetime by taking ownership, the ttl is carried over unchanged.
+    #[inline]
+    pub fn into_owned(self) -> TimedOption<alloc::borrow::Cow<'static, T>, B> {
+        TimedOption {
+            value: self
+                .value
+                .map(|value| alloc::borrow::Cow::Owned(value.into_owned())),
+            ttl: self.ttl,
+        }
+    }
+}
+
+impl<T, B> TimedOption<TimedOption<T, B>, B>
+where
+    B: TtlBackend + Ord,
+{
+    /// Removes one level of nesting, the result gets the earlier of the two
+    /// ttls so it is only valid while both layers are.
+    #[inline]
+    pub fn flatten(self) -> TimedOption<T, B> {
+        match self.value {
+            Some(inner) => TimedOption {
+                value: inner.value,
+                ttl: core::cmp::min(self.ttl, inner.ttl),
+            },
+            None => TimedOption::empty(),
+        }
+    }
+}
+
+impl<T, E, B> TimedOption<Result<T, E>, B>
+where
+    B: TtlBackend,
+{
+    /// Transposes a timed-option of a `Result` into a `Result` of a
+    /// timed-option, the deadline is kept on the `Ok` side.
+    ///
+    /// An expired `Ok` or a `None` gives `Ok` of an
+    /// [empty](TimedOption::empty) timed-option, while an `Err` is surfaced
+    /// even if it has expired.
+    #[inline]
+    pub fn transpose(self) -> Result<TimedOption<T, B>, E> {
+        let valid = self.ttl.is_valid();
+        match self.value {
+            Some(Ok(value)) if valid => Ok(TimedOption::with_deadline(value, self.ttl)),
+            Some(Err(err)) => Err(err),
+            _ => Ok(TimedOption::empty()),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> TimedOption<T, tokio::time::Instant> {
+    /// Returns a future that completes once the ttl has elapsed, right away
+    /// if it already has. Handy to `tokio::select!` on expiry.
+    #[inline]
+    pub fn expiry(&self) -> tokio::time::Sleep {
+        tokio::time::sleep_until(self.ttl)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> TimedOption<T, std::time::Instant> {
+    /// Returns a future that completes once the ttl has elapsed, right away
+    /// if it already has. Handy to `tokio::select!` on expiry.
+    #[inline]
+    pub fn expiry(&self) -> tokio::time::Sleep {
+        tokio::time::sleep_until(tokio::time::Instant::from_std(self.ttl))
+    }
+}
+
+impl<T, B> Default for TimedOption<T, B>
+where
+    B: TtlBackend,
+{
+    /// Returns an [empty](TimedOption::empty) timed-option.
+    #[inline]
+    fn default() -> Self {
+        TimedOption::empty()
+    }
+}
+
+/// Timed-options are compared by their observable state, see
+/// [`as_timed_value`](TimedOption::as_timed_value), never by their raw ttls.
+///
+/// Two timed-options are equal if both are `None`, both are valid with equal
+/// values, or both are expired with equal values.
+impl<T, B> PartialEq for TimedOption<T, B>
+where
+    T: PartialEq,
+    B: TtlBackend,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_timed_value() == other.as_timed_value()
+    }
+}
+
+impl<T, B> Eq for TimedOption<T, B>
+where
+    T: Eq,
+    B: TtlBackend,
+{
+}
+
+/// Compares the observable state with a [`TimedValue`], so an expired
+/// timed-option equals `TimedValue::Expired` of its value.
+impl<T, B> PartialEq<TimedValue<T>> for TimedOption<T, B>
+where
+    T: PartialEq,
+    B: TtlBackend,
+{
+    #[inline]
+    fn eq(&self, other: &TimedValue<T>) -> bool {
+        self.as_timed_value() == other.as_ref()
+    }
+}
+
+/// Hashes the same observable state as [`PartialEq`], so equal timed-options
+/// hash equally. The clock is read once and the state at that instant is
+/// hashed, a valid key may expire and change its hash while in a map.
+impl<T, B> core::hash::Hash for TimedOption<T, B>
+where
+    T: core::hash::Hash,
+    B: TtlBackend,
+{
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_timed_value().hash(state);
+    }
+}
+
+/// Formats the observable state as `Some(<value>)`, `Stale(<value>)` or
+/// `None`, the ttl itself is not shown.
+impl<T, B> core::fmt::Display for TimedOption<T, B>
+where
+    T: core::fmt::Display,
+    B: TtlBackend,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.as_timed_value() {
+            TimedValue::Valid(value) => write!(f, "Some({value})"),
+            TimedValue::Expired(value) => write!(f, "Stale({value})"),
+            TimedValue::None => f.write_str("None"),
+        }
+    }
+}
+
+/// Orders timed-options by their deadline alone, the soonest to expire sorts
+/// first. The value is ignored entirely, also for equality.
+///
+/// [`TimedOption`] itself is not `Ord` since its equality compares the
+/// observable state. Use [`core::cmp::Reverse`] to pop the next expiry from a
+/// max-heap like `BinaryHeap`.
+#[derive(Debug, Copy, Clone)]
+pub struct ByDeadline<T, B>(pub TimedOption<T, B>);
+
+impl<T, B> PartialEq for ByDeadline<T, B>
+where
+    B: TtlBackend + Ord,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ttl == other.0.ttl
+    }
+}
+
+impl<T, B> Eq for ByDeadline<T, B> where B: TtlBackend + Ord {}
+
+impl<T, B> PartialOrd for ByDeadline<T, B>
+where
+    B: TtlBackend + Ord,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, B> Ord for ByDeadline<T, B>
+where
+    B: TtlBackend + Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.ttl.cmp(&other.0.ttl)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Timed Option Aged
+////////////////////////////////////////////////////////////////////////////////
+
+/// A [`TimedOption`] that also remembers when its value was stored and the
+/// ttl it was given.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: serde::Serialize, Ttl: serde::Serialize, Ttl::Duration: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de>, Ttl: serde::Deserialize<'de>, \
+                       Ttl::Duration: serde::Deserialize<'de>"
+    ))
+)]
+pub struct TimedOptionAged<T, Ttl: TtlBackend> {
+    inner: TimedOption<T, Ttl>,
+    created_at: Ttl,
+    lifetime: Option<Ttl::Duration>,
+}
+
+impl<T, B> TimedOptionAged<T, B>
+where
+    B: TtlBackend,
+{
+    /// Some value of type `T` with a ttl, created now.
+    #[inline]
+    pub fn new(value: T, ttl: B::Duration) -> Self
+    where
+        B::Duration: Clone,
+    {
+        let now = B::now();
+        TimedOptionAged {
+            inner: TimedOption::with_deadline(value, now.clone().add(ttl.clone())),
+            created_at: now,
+            lifetime: Some(ttl),
+        }
+    }
+
+    /// No value with a expired ttl and no creation time
+    #[inline]
+    pub fn empty() -> Self {
+        TimedOptionAged {
+            inner: TimedOption::empty(),
+            created_at: B::expired(),
+            lifetime: None,
+        }
+    }
+
+    /// Replaces the value and ttl, the creation time is reset to now
+    #[inline]
+    pub fn set(&mut self, value: T, ttl: B::Duration)
+    where
+        B::Duration: Clone,
+    {
+        *self = TimedOptionAged::new(value, ttl);
+    }
+
+    /// Re-stamps the deadline to now plus the ttl the value was created
+    /// with, even if it already expired. The creation time is kept and an
+    /// empty option stays empty.
+    #[inline]
+    pub fn reset(&mut self)
+    where
+        B::Duration: Clone,
+    {
+        if let (Some(_), Some(lifetime)) = (&self.inner.value, &self.lifetime) {
+            self.inner.ttl = B::now().add(lifetime.clone());
+        }
+    }
+
+    /// The ttl the value was created with, `None` if there is no value
+    #[inline]
+    pub fn lifetime(&self) -> Option<&B::Duration> {
+        self.inner.value.as_ref().and(self.lifetime.as_ref())
+    }
+
+    /// Fraction of the lifetime that has elapsed, from `0.0` right after
+    /// creation or a [`reset`](Self::reset) to `1.0` at or past the deadline.
+    /// `None` if there is no value.
+    #[inline]
+    pub fn progress(&self) -> Option<f64>
+    where
+        B::Duration: DurationStd,
+    {
+        let lifetime = self.lifetime()?.to_std();
+        let remaining = self
+            .inner
+            .ttl
+            .remaining()
+            .map_or(core::time::Duration::ZERO, |dt| dt.to_std());
+        match lifetime.is_zero() {
+            true => Some(1.0),
+            false => Some((1.0 - remaining.as_secs_f64() / lifetime.as_secs_f64()).clamp(0.0, 1.0)),
+        }
+    }
+
+    /// When the value was stored, `None` if there is no value
+    #[inline]
+    pub fn created_at(&self) -> Option<&B> {
+        self.inner.value.as_ref().map(|_| &self.created_at)
+    }
+
+    /// How long ago the value was stored, `None` if there is no value
+    #[inline]
+    pub fn age(&self) -> Option<B::Duration> {
+        self.created_at()?.elapsed_past()
+    }
+
+    /// Borrows the underlying timed option
+    #[inline]
+    pub fn as_timed_option(&self) -> &TimedOption<T, B> {
+        &self.inner
+    }
+
+    /// Drops the creation time and returns the underlying timed option
+    #[inline]
+    pub fn into_timed_option(self) -> TimedOption<T, B> {
+        self.inner
+    }
+
+    /// See [`TimedOption::into_option`]
+    #[inline]
+    pub fn into_option(self) -> Option<T> {
+        self.inner.into_option()
+    }
+
+    /// See [`TimedOption::as_option`]
+    #[inline]
+    pub fn as_option(&self) -> Option<&T> {
+        self.inner.as_option()
+    }
+
+    /// See [`TimedOption::into_timed_value`]
+    #[inline]
+    pub fn into_timed_value(self) -> TimedValue<T> {
+        self.inner.into_timed_value()
+    }
+
+    /// See [`TimedOption::as_timed_value`]
+    #[inline]
+    pub fn as_timed_value(&self) -> TimedValue<&T> {
+        self.inner.as_timed_value()
+    }
+
+    /// See [`TimedOption::is_some`]
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// See [`TimedOption::is_none`]
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl<T, B> Default for TimedOptionAged<T, B>
+where
+    B: TtlBackend,
+{
+    #[inline]
+    fn default() -> Self {
+        TimedOptionAged::empty()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Sliding Timed Option
+////////////////////////////////////////////////////////////////////////////////
+
+/// A [`TimedOption`] whose deadline moves to now plus a fixed window each time
+/// a still valid value is accessed through [`get`](SlidingTimedOption::get).
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: serde::Serialize, Ttl: serde::Serialize, Ttl::Duration: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de>, Ttl: serde::Deserialize<'de>, \
+                       Ttl::Duration: serde::Deserialize<'de>"
+    ))
+)]
+pub struct SlidingTimedOption<T, Ttl: TtlBackend> {
+    inner: TimedOption<T, Ttl>,
+    window: Ttl::Duration,
+}
+
+impl<T, B> SlidingTimedOption<T, B>
+where
+    B: TtlBackend,
+    B::Duration: Clone,
+{
+    /// Some value of type `T` valid for `window` after its last access
+    #[inline]
+    pub fn new(value: T, window: B::Duration) -> Self {
+        SlidingTimedOption {
+            inner: TimedOption::new(value, window.clone()),
+            window,
+        }
+    }
+
+    /// No value with a expired ttl
+    #[inline]
+    pub fn empty(window: B::Duration) -> Self {
+        SlidingTimedOption {
+            inner: TimedOption::empty(),
+            window,
+        }
+    }
+
+    /// Returns the value if it is still valid and slides its deadline to now
+    /// plus the window. Expired values are not resurrected.
+    #[inline]
+    pub fn get(&mut self) -> Option<&T> {
+        match self.inner.is_some() {
+            true => {
+                self.inner.ttl = B::now().add(self.window.clone());
+                self.inner.value.as_ref()
+            }
+            false => None,
+        }
+    }
+
+    /// Replaces the value, valid for one window from now
+    #[inline]
+    pub fn set(&mut self, value: T) {
+        self.inner.set(value, self.window.clone());
+    }
+
+    /// The window added on every access
+    #[inline]
+    pub fn window(&self) -> &B::Duration {
+        &self.window
+    }
+
+    /// Borrows the value without sliding the deadline, see
+    /// [`TimedOption::as_option`]
+    #[inline]
+    pub fn as_option(&self) -> Option<&T> {
+        self.inner.as_option()
+    }
+
+    /// See [`TimedOption::into_option`]
+    #[inline]
+    pub fn into_option(self) -> Option<T> {
+        self.inner.into_option()
+    }
+
+    /// Borrows the underlying timed option
+    #[inline]
+    pub fn as_timed_option(&self) -> &TimedOption<T, B> {
+        &self.inner
+    }
+
+    /// Drops the window and returns the underlying timed option
+    #[inline]
+    pub fn into_timed_option(self) -> TimedOption<T, B> {
+        self.inner
+    }
+
+    /// See [`TimedOption::is_some`]
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// See [`TimedOption::is_none`]
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Timed Option Builder
+////////////////////////////////////////////////////////////////////////////////
+
+/// Builds a [`TimedOption`] or a [`SlidingTimedOption`] step by step.
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use std::time::{Duration, Instant};
+/// use timed_option::TimedOptionBuilder;
+///
+/// let token = TimedOptionBuilder::<_, Instant>::new()
+///     .value("token")
+///     .ttl(Duration::from_secs(60))
+///     .build();
+/// assert!(token.is_some());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimedOptionBuilder<T, B: TtlBackend> {
+    value: Option<T>,
+    ttl: Option<B::Duration>,
+    deadline: Option<B>,
+    jitter: Option<Jitter<B::Duration>>,
+    window: Option<B::Duration>,
+}
+
+/// A jitter and the function applying it to a ttl.
+type Jitter<D> = (D, fn(D, D) -> D);
+
+impl<T, B> TimedOptionBuilder<T, B>
+where
+    B: TtlBackend,
+{
+    /// A builder with nothing set, building an empty timed-option
+    #[inline]
+    pub fn new() -> Self {
+        TimedOptionBuilder {
+            value: None,
+            ttl: None,
+            deadline: None,
+            jitter: None,
+            window: None,
+        }
+    }
+
+    /// Sets the value
+    #[inline]
+    pub fn value(mut self, value: T) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Sets a ttl counted from when the timed-option is built
+    #[inline]
+    pub fn ttl(mut self, ttl: B::Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Sets an absolute deadline
+    #[inline]
+    pub fn deadline(mut self, deadline: B) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Shifts the ttl by a random offset, see [`TimedOption::new_jittered`]
+    #[cfg(feature = "rand")]
+    #[inline]
+    pub fn jitter(mut self, jitter: B::Duration) -> Self
+    where
+        B: TtlBackend<Duration = core::time::Duration>,
+    {
+        self.jitter = Some((jitter, jittered));
+        self
+    }
+
+    /// Sets the window of a [`SlidingTimedOption`], see
+    /// [`build_sliding`](Self::build_sliding)
+    #[inline]
+    pub fn sliding(mut self, window: B::Duration) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    /// Builds the timed-option. Without a value it is empty and without a ttl
+    /// or deadline the value is expired.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both a ttl and a deadline are set, if a jitter is set
+    /// without a ttl, or if a sliding window is set.
+    #[inline]
+    #[track_caller]
+    pub fn build(self) -> TimedOption<T, B> {
+        if self.window.is_some() {
+            panic!("a sliding window is set, use `TimedOptionBuilder::build_sliding()`");
+        }
+        let ttl = match (self.ttl, self.jitter) {
+            (Some(ttl), Some((jitter, apply))) => Some(apply(ttl, jitter)),
+            (ttl, None) => ttl,
+            (None, Some(_)) => panic!("a jitter needs a ttl to be set"),
+        };
+        let deadline = match (ttl, self.deadline) {
+            (Some(_), Some(_)) => panic!("both a ttl and a deadline are set"),
+            (Some(ttl), None) => B::now().add(ttl),
+            (None, Some(deadline)) => deadline,
+            (None, None) => B::expired(),
+        };
+        match self.value {
+            Some(value) => TimedOption::with_deadline(value, deadline),
+            None => TimedOption::empty(),
+        }
+    }
+
+    /// Builds a sliding timed-option with the [`sliding`](Self::sliding)
+    /// window. Without a value it is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no window is set, or if a ttl, deadline or jitter is set.
+    #[inline]
+    #[track_caller]
+    pub fn build_sliding(self) -> SlidingTimedOption<T, B>
+    where
+        B::Duration: Clone,
+    {
+        if self.ttl.is_some() || self.deadline.is_some() || self.jitter.is_some() {
+            panic!("a sliding timed-option only takes a window");
+        }
+        let Some(window) = self.window else {
+            panic!("a sliding window needs to be set");
+        };
+        match self.value {
+            Some(value) => SlidingTimedOption::new(value, window),
+            None => SlidingTimedOption::empty(window),
+        }
+    }
+}
+
+impl<T, B> Default for TimedOptionBuilder<T, B>
+where
+    B: TtlBackend,
+{
+    #[inline]
+    fn default() -> Self {
+        TimedOptionBuilder::new()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Atomic Timed Option
+////////////////////////////////////////////////////////////////////////////////
+
+/// A [`TimedOption`] that can be shared between threads and updated without
+/// locking, backed by an [`arc_swap::ArcSwap`].
+///
+/// The value and its deadline are swapped together, so a [`load`] never
+/// pairs a value with the deadline of another [`store`]. A `load` sees the
+/// latest `store` that happened before it, with the same guarantees as an
+/// acquire load of a release store.
+///
+/// [`load`]: AtomicTimedOption::load
+/// [`store`]: AtomicTimedOption::store
+#[cfg(feature = "arc-swap")]
+#[derive(Debug)]
+pub struct AtomicTimedOption<T, B> {
+    inner: arc_swap::ArcSwap<TimedOption<T, B>>,
+}
+
+#[cfg(feature = "arc-swap")]
+impl<T, B> AtomicTimedOption<T, B>
+where
+    B: TtlBackend,
+{
+    /// Some value of type `T` with a ttl
+    #[inline]
+    pub fn new(value: T, ttl: B::Duration) -> Self {
+        AtomicTimedOption {
+            inner: arc_swap::ArcSwap::from_pointee(TimedOption::new(value, ttl)),
+        }
+    }
+
+    /// No value with a expired ttl
+    #[inline]
+    pub fn empty() -> Self {
+        AtomicTimedOption {
+            inner: arc_swap::ArcSwap::from_pointee(TimedOption::empty()),
+        }
+    }
+
+    /// Returns a snapshot of the current state as a `TimedValue<T>`
+    #[inline]
+    pub fn load(&self) -> TimedValue<T>
+    where
+        T: Clone,
+    {
+        self.inner.load().as_timed_value().map(T::clone)
+    }
+
+    /// Replaces the value and ttl
+    #[inline]
+    pub fn store(&self, value: T, ttl: B::Duration) {
+        self.inner
+            .store(std::sync::Arc::new(TimedOption::new(value, ttl)));
+    }
+
+    /// Removes the value
+    #[inline]
+    pub fn clear(&self) {
+        self.inner.store(std::sync::Arc::new(TimedOption::empty()));
+    }
+}
+
+#[cfg(feature = "arc-swap")]
+impl<T, B> Default for AtomicTimedOption<T, B>
+where
+    B: TtlBackend,
+{
+    #[inline]
+    fn default() -> Self {
+        AtomicTimedOption::empty()
+    }
+}
+
+#[cfg(feature = "arc-swap")]
+impl<T, B> From<TimedOption<T, B>> for AtomicTimedOption<T, B> {
+    #[inline]
+    fn from(value: TimedOption<T, B>) -> Self {
+        AtomicTimedOption {
+            inner: arc_swap::ArcSwap::from_pointee(value),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Compact Timed Option
+////////////////////////////////////////////////////////////////////////////////
+
+/// A [`TimedOption`] that stores the empty state in a niche of `T` or of the
+/// deadline instead of next to the value, so it is no larger than `(T, B)`
+/// whenever one of them has a niche, as [`Instant`](std::time::Instant) does.
+///
+/// An empty compact timed-option has no deadline at all.
+#[derive(Debug, Copy, Clone)]
+pub struct CompactTimedOption<T, B>(Compact<T, B>);
+
+#[derive(Debug, Copy, Clone)]
+enum Compact<T, B> {
+    Some(T, B),
+    None,
+}
+
+impl<T, B> CompactTimedOption<T, B>
+where
+    B: TtlBackend,
+{
+    /// Some value of type `T` with a ttl
+    #[inline]
+    pub fn new(value: T, ttl: B::Duration) -> Self {
+        CompactTimedOption(Compact::Some(value, B::now().add(ttl)))
+    }
+
+    /// Some value of type `T` valid until the absolute `deadline`
+    #[inline]
+    pub fn with_deadline(value: T, deadline: B) -> Self {
+        CompactTimedOption(Compact::Some(value, deadline))
+    }
+
+    /// No value and no deadline
+    #[inline]
+    pub fn empty() -> Self {
+        CompactTimedOption(Compact::None)
+    }
+
+    /// Returns the deadline, `None` if there is no value
+    #[inline]
+    pub fn expires_at(&self) -> Option<&B> {
+        match &self.0 {
+            Compact::Some(_, deadline) => Some(deadline),
+            Compact::None => None,
+        }
+    }
+
+    /// See [`TimedOption::into_option`]
+    #[inline]
+    pub fn into_option(self) -> Option<T> {
+        self.into_timed_value().into_option()
+    }
+
+    /// See [`TimedOption::as_option`]
+    #[inline]
+    pub fn as_option(&self) -> Option<&T> {
+        self.as_timed_value().into_option()
+    }
+
+    /// See [`TimedOption::into_timed_value`]
+    #[inline]
+    pub fn into_timed_value(self) -> TimedValue<T> {
+        match self.0 {
+            Compact::Some(value, deadline) => match deadline.is_valid() {
+                true => TimedValue::Valid(value),
+                false => TimedValue::Expired(value),
+            },
+            Compact::None => TimedValue::None,
+        }
+    }
+
+    /// See [`TimedOption::as_timed_value`]
+    #[inline]
+    pub fn as_timed_value(&self) -> TimedValue<&T> {
+        match &self.0 {
+            Compact::Some(value, deadline) => match deadline.is_valid() {
+                true => TimedValue::Valid(value),
+                false => TimedValue::Expired(value),
+            },
+            Compact::None => TimedValue::None,
+        }
+    }
+
+    /// See [`TimedOption::is_some`]
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.as_timed_value().is_valid()
+    }
+
+    /// See [`TimedOption::is_none`]
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+}
+
+impl<T, B> Default for CompactTimedOption<T, B>
+where
+    B: TtlBackend,
+{
+    #[inline]
+    fn default() -> Self {
+        CompactTimedOption::empty()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Timed Value
+////////////////////////////////////////////////////////////////////////////////
+
+/// An enum representing a value that is associated with a time validity status.
+///
+/// `TimedValue` can be used to indicate whether a value is valid, expired, or absent (none).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TimedValue<T> {
+    /// value of type `T` that is considered valid
+    Valid(T),
+    /// value of type `T` that has expired and is no longer considered valid
+    Expired(T),
+    /// Indicates the absence of a value.
+    None,
+}
+
+impl<T> TimedValue<T> {
+    /// Returns `true` if the [`TimedValue`]` is `Valid`.
+    #[inline]
+    pub const fn is_valid(&self) -> bool {
+        match self {
+            TimedValue::Valid(_) => true,
+            TimedValue::Expired(_) => false,
+            TimedValue::None => false,
+        }
+    }
+
+    /// Returns `true` if the TimedValue is `Expired`.
+    #[inline]
+    pub const fn is_expired(&self) -> bool {
+        match self {
+            TimedValue::Valid(_) => false,
+            TimedValue::Expired(_) => true,
+            TimedValue::None => false,
+        }
+    }
+
+    /// Returns `true` if the TimedValue is `None`.
+    #[inline]
+    pub const fn is_none(&self) -> bool {
+        match self {
+            TimedValue::Valid(_) => false,
+            TimedValue::Expired(_) => false,
+            TimedValue::None => true,
+        }
+    }
+
+    /// Returns `true` if the TimedValue is `Valid` or `Expired`.
+    #[inline]
+    pub const fn has_value(&self) -> bool {
+        match self {
+            TimedValue::Valid(_) => true,
+            TimedValue::Expired(_) => true,
+            TimedValue::None => false,
+        }
+    }
+
+    /// Converts from `&TimedValue<T>` to `TimedValue<&T>`.
+    #[inline]
+    pub const fn as_ref(&self) -> TimedValue<&T> {
+        match *self {
+            TimedValue::Valid(ref inner) => TimedValue::Valid(inner),
+            TimedValue::Expired(ref inner) => TimedValue::Expired(inner),
+            TimedValue::None => TimedValue::None,
+        }
+    }
+
+    /// Converts from `&TimedValue<T>` to `TimedValue<&T::Target>`.
+    #[inline]
+    pub fn as_deref(&self) -> TimedValue<&T::Target>
+    where
+        T: core::ops::Deref,
+    {
+        self.as_ref().map(|inner| inner.deref())
+    }
+
+    /// Converts from `&mut TimedValue<T>` to `TimedValue<&mut T>`.
+    #[inline]
+    pub fn as_mut(&mut self) -> TimedValue<&mut T> {
+        match *self {
+            TimedValue::Valid(ref mut inner) => TimedValue::Valid(inner),
+            TimedValue::Expired(ref mut inner) => TimedValue::Expired(inner),
+            TimedValue::None => TimedValue::None,
+        }
+    }
+
+    /// Returns `Some` for a `Valid` value, an expired value gives `None`.
+    #[inline]
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            TimedValue::Valid(value) => Some(value),
+            TimedValue::Expired(_) => None,
+            TimedValue::None => None,
+        }
+    }
+
+    /// Returns `Some` for a `Valid` or `Expired` value.
+    #[inline]
+    pub fn into_option_any(self) -> Option<T> {
+        match self {
+            TimedValue::Valid(value) => Some(value),
+            TimedValue::Expired(value) => Some(value),
+            TimedValue::None => None,
+        }
+    }
+
+    /// Returns `Some` reference for a `Valid` value, an expired value gives
+    /// `None`.
+    #[inline]
+    pub const fn as_option(&self) -> Option<&T> {
+        match *self {
+            TimedValue::Valid(ref inner) => Some(inner),
+            TimedValue::Expired(_) => None,
+            TimedValue::None => None,
+        }
+    }
+
+    /// Returns `Some` reference for a `Valid` or `Expired` value.
+    #[inline]
+    pub const fn as_option_any(&self) -> Option<&T> {
+        match *self {
+            TimedValue::Valid(ref inner) => Some(inner),
+            TimedValue::Expired(ref inner) => Some(inner),
+            TimedValue::None => None,
+        }
+    }
+
+    /// Returns `Some` only for a `Valid` value.
+    #[inline]
+    pub fn valid(self) -> Option<T> {
+        self.into_option()
+    }
+
+    /// Returns `Some` only for an `Expired` value.
+    #[inline]
+    pub fn expired(self) -> Option<T> {
+        match self {
+            TimedValue::Valid(_) => None,
+            TimedValue::Expired(value) => Some(value),
+            TimedValue::None => None,
+        }
+    }
+
+    /// Returns `Some` reference only for a `Valid` value.
+    #[inline]
+    pub const fn valid_ref(&self) -> Option<&T> {
+        self.as_option()
+    }
+
+    /// Returns `Some` reference only for an `Expired` value.
+    #[inline]
+    pub const fn expired_ref(&self) -> Option<&T> {
+        match *self {
+            TimedValue::Valid(_) => None,
+            TimedValue::Expired(ref inner) => Some(inner),
+            TimedValue::None => None,
+        }
+    }
+
+    /// Returns an iterator yielding the value only if it is `Valid`.
+    #[inline]
+    pub fn iter_valid(&self) -> core::option::IntoIter<&T> {
+        self.as_option().into_iter()
+    }
+
+    /// Returns an iterator yielding the value if it is `Valid` or `Expired`.
+    #[inline]
+    pub fn iter_any(&self) -> core::option::IntoIter<&T> {
+        self.as_option_any().into_iter()
+    }
+
+    /// Transforms the `TimedValue<T>` into a `Result<T, E>`, mapping `Valid`
+    /// to `Ok` and both `Expired` and `None` to `Err(err)`.
+    #[inline]
+    pub fn ok_or<E>(self, err: E) -> Result<T, E> {
+        self.into_option().ok_or(err)
+    }
+
+    /// Transforms the `TimedValue<T>` into a `Result<T, E>` accepting stale
+    /// values, mapping `Valid` and `Expired` to `Ok` and `None` to `Err(err)`.
+    #[inline]
+    pub fn ok_or_stale<E>(self, err: E) -> Result<T, E> {
+        self.into_option_any().ok_or(err)
+    }
+
+    /// Returns the contained `Valid` or `Expired` value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None`.
+    #[inline]
+    #[track_caller]
+    pub fn unwrap(self) -> T {
+        match self {
+            TimedValue::Valid(value) => value,
+            TimedValue::Expired(value) => value,
+            TimedValue::None => panic!("called `TimedValue::unwrap()` on a `None` value"),
+        }
+    }
+
+    /// Returns the contained `Valid` or `Expired` value.
+    ///
+    /// # Panics
+    ///
+    /// Panics with `msg` if the value is `None`.
+    #[inline]
+    #[track_caller]
+    pub fn expect(self, msg: &str) -> T {
+        match self {
+            TimedValue::Valid(value) => value,
+            TimedValue::Expired(value) => value,
+            TimedValue::None => panic!("{msg}"),
+        }
+    }
+
+    /// Returns the contained `Valid` value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `Expired` or `None`.
+    #[inline]
+    #[track_caller]
+    pub fn unwrap_valid(self) -> T {
+        match self {
+            TimedValue::Valid(value) => value,
+            TimedValue::Expired(_) => {
+                panic!("called `TimedValue::unwrap_valid()` on an `Expired` value")
+            }
+            TimedValue::None => panic!("called `TimedValue::unwrap_valid()` on a `None` value"),
+        }
+    }
+
+    /// Returns the contained `Valid` or `Expired` value or `default`.
+    ///
+    /// An expired value is returned as is, like [`has_value`](Self::has_value)
+    /// only `None` falls back to the default.
+    #[inline]
+    pub fn unwrap_or(self, default: T) -> T {
+        match self {
+            TimedValue::Valid(value) => value,
+            TimedValue::Expired(value) => value,
+            TimedValue::None => default,
+        }
+    }
+
+    /// Returns the contained `Valid` or `Expired` value or computes it from
+    /// `f`. Only `None` calls `f`.
+    #[inline]
+    pub fn unwrap_or_else<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        match self {
+            TimedValue::Valid(value) => value,
+            TimedValue::Expired(value) => value,
+            TimedValue::None => f(),
+        }
+    }
+
+    /// Returns the contained `Valid` or `Expired` value or `T::default()`.
+    /// Only `None` falls back to the default.
+    #[inline]
+    pub fn unwrap_or_default(self) -> T
+    where
+        T: Default,
+    {
+        self.unwrap_or_else(T::default)
+    }
+
+    /// Maps a `TimedValue<T>` to a `TimedValue<U>` by applying a function to
+    /// the contained value, keeping the variant.
+    #[inline]
+    pub fn map<U, F>(self, f: F) -> TimedValue<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            TimedValue::Valid(value) => TimedValue::Valid(f(value)),
+            TimedValue::Expired(value) => TimedValue::Expired(f(value)),
+            TimedValue::None => TimedValue::None,
+        }
+    }
+
+    /// Applies `f` to a `Valid` value, `Expired` and `None` are passed through
+    /// unchanged.
+    #[inline]
+    pub fn map_valid<F>(self, f: F) -> Self
+    where
+        F: FnOnce(T) -> T,
+    {
+        match self {
+            TimedValue::Valid(value) => TimedValue::Valid(f(value)),
+            other => other,
+        }
+    }
+
+    /// Applies `f` to an `Expired` value, `Valid` and `None` are passed through
+    /// unchanged.
+    #[inline]
+    pub fn map_expired<F>(self, f: F) -> Self
+    where
+        F: FnOnce(T) -> T,
+    {
+        match self {
+            TimedValue::Expired(value) => TimedValue::Expired(f(value)),
+            other => other,
+        }
+    }
+
+    /// Calls `f` with a `Valid` value and returns the result, `Expired` and
+    /// `None` short-circuit to `None`.
+    #[inline]
+    pub fn and_then<U, F>(self, f: F) -> TimedValue<U>
+    where
+        F: FnOnce(T) -> TimedValue<U>,
+    {
+        match self {
+            TimedValue::Valid(value) => f(value),
+            TimedValue::Expired(_) => TimedValue::None,
+            TimedValue::None => TimedValue::None,
+        }
+    }
+
+    /// Returns `None` for a `Valid` value that fails `predicate`. `Expired`
+    /// and `None` are passed through unchanged without calling `predicate`.
+    #[inline]
+    pub fn filter<P>(self, predicate: P) -> Self
+    where
+        P: FnOnce(&T) -> bool,
+    {
+        match self {
+            TimedValue::Valid(value) if !predicate(&value) => TimedValue::None,
+            other => other,
+        }
+    }
+}
+
+impl<T> Default for TimedValue<T> {
+    /// Returns [`TimedValue::None`].
+    #[inline]
+    fn default() -> Self {
+        TimedValue::None
+    }
+}
+
+/// Formats as `valid: <value>`, `expired: <value>` or `none`.
+impl<T> core::fmt::Display for TimedValue<T>
+where
+    T: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TimedValue::Valid(value) => write!(f, "valid: {value}"),
+            TimedValue::Expired(value) => write!(f, "expired: {value}"),
+            TimedValue::None => f.write_str("none"),
+        }
+    }
+}
+
+/// The state of a timed-option without its value, see
+/// [`TimedOption::state`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TimedState {
+    /// A value that has not expired.
+    Fresh,
+    /// A value that has expired.
+    Stale,
+    /// No value.
+    Empty,
+}
+
+/// Why a timed-option had no valid value, see [`TimedOption::into_result`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TimedError {
+    /// The value has expired.
+    Expired,
+    /// There is no value.
+    Absent,
+}
+
+impl core::fmt::Display for TimedError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TimedError::Expired => f.write_str("value expired"),
+            TimedError::Absent => f.write_str("value absent"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TimedError {}
+
+////////////////////////////////////////////////////////////////////////////////
+// Collections
+////////////////////////////////////////////////////////////////////////////////
+
+/// Removes every entry of `map` that is `None` or has expired, returning how
+/// many were removed.
+#[cfg(feature = "std")]
+pub fn sweep_expired<K, V, B, S>(
+    map: &mut std::collections::HashMap<K, TimedOption<V, B>, S>,
+) -> usize
+where
+    B: TtlBackend,
+{
+    let len = map.len();
+    map.retain(|_, value| value.is_some());
+    len - map.len()
+}
+
+/// Returns the timed-option with the earliest deadline, the first one on a
+/// tie. Empty timed-options take part with their expired deadline.
+#[inline]
+pub fn soonest<T, B, I>(iter: I) -> Option<TimedOption<T, B>>
+where
+    B: TtlBackend + Ord,
+    I: IntoIterator<Item = TimedOption<T, B>>,
+{
+    iter.into_iter().min_by(|a, b| a.ttl.cmp(&b.ttl))
+}
+
+/// Returns the timed-option with the latest deadline, the last one on a tie.
+/// Empty timed-options take part with their expired deadline.
+#[inline]
+pub fn latest<T, B, I>(iter: I) -> Option<TimedOption<T, B>>
+where
+    B: TtlBackend + Ord,
+    I: IntoIterator<Item = TimedOption<T, B>>,
+{
+    iter.into_iter().max_by(|a, b| a.ttl.cmp(&b.ttl))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Conversion
+////////////////////////////////////////////////////////////////////////////////
+
+impl<T, B> From<TimedOption<T, B>> for Option<T>
+where
+    B: TtlBackend,
+{
+    #[inline]
+    fn from(value: TimedOption<T, B>) -> Self {
+        value.into_option()
+    }
+}
+
+impl<T, B> From<TimedOption<T, B>> for TimedValue<T>
+where
+    B: TtlBackend,
+{
+    #[inline]
+    fn from(value: TimedOption<T, B>) -> Self {
+        value.into_timed_value()
+    }
+}
+
+impl<T, B> From<TimedOptionAged<T, B>> for TimedOption<T, B>
+where
+    B: TtlBackend,
+{
+    #[inline]
+    fn from(value: TimedOptionAged<T, B>) -> Self {
+        value.into_timed_option()
+    }
+}
+
+impl<T, B> From<SlidingTimedOption<T, B>> for TimedOption<T, B>
+where
+    B: TtlBackend,
+    B::Duration: Clone,
+{
+    #[inline]
+    fn from(value: SlidingTimedOption<T, B>) -> Self {
+        value.into_timed_option()
+    }
+}
+
+/// An empty timed-option loses its deadline.
+impl<T, B> From<TimedOption<T, B>> for CompactTimedOption<T, B>
+where
+    B: TtlBackend,
+{
+    #[inline]
+    fn from(value: TimedOption<T, B>) -> Self {
+        match value.value {
+            Some(inner) => CompactTimedOption::with_deadline(inner, value.ttl),
+            None => CompactTimedOption::empty(),
+        }
+    }
+}
+
+impl<T, B> From<CompactTimedOption<T, B>> for TimedOption<T, B>
+where
+    B: TtlBackend,
+{
+    #[inline]
+    fn from(value: CompactTimedOption<T, B>) -> Self {
+        match value.0 {
+            Compact::Some(inner, deadline) => TimedOption::with_deadline(inner, deadline),
+            Compact::None => TimedOption::empty(),
+        }
+    }
+}
+
+/// Maps `Some` to `Valid` and `None` to `None`.
+impl<T> From<Option<T>> for TimedValue<T> {
+    #[inline]
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => TimedValue::Valid(value),
+            None => TimedValue::None,
+        }
+    }
+}
+
+/// Maps `Valid` to `Some` and both `Expired` and `None` to `None`, this is
+/// lossy as an expired value is dropped.
+impl<T> From<TimedValue<T>> for Option<T> {
+    #[inline]
+    fn from(value: TimedValue<T>) -> Self {
+        value.into_option()
+    }
+}
+
+impl<T, B> IntoIterator for TimedOption<T, B>
+where
+    B: TtlBackend,
+{
+    type Item = T;
+    type IntoIter = core::option::IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_option().into_iter()
+    }
+}
+
+impl<'a, T, B> IntoIterator for &'a TimedOption<T, B>
+where
+    B: TtlBackend,
+{
+    type Item = &'a T;
+    type IntoIter = core::option::IntoIter<&'a T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterates over a `Valid` value only, see [`TimedValue::iter_any`] to
+/// include expired ones.
+impl<T> IntoIterator for TimedValue<T> {
+    type Item = T;
+    type IntoIter = core::option::IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_option().into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a TimedValue<T> {
+    type Item = &'a T;
+    type IntoIter = core::option::IntoIter<&'a T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_valid()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// TTL Backent
+////////////////////////////////////////////////////////////////////////////////
+
+/// The clock a [`TimedOption`] measures its ttl with, implemented by the
+/// deadline type it stores.
+///
+/// A deadline is valid strictly before it is reached: once `now() >= deadline`
+/// it is expired. A value is so valid on the half-open interval
+/// `[now, now + ttl)`, and a zero ttl is expired right away.
+pub trait TtlBackend: Clone {
+    type Duration;
+
+    fn now() -> Self;
+    fn expired() -> Self;
+    fn add(self, dt: Self::Duration) -> Self;
+    /// Like [`add`](Self::add), but `None` if the deadline is out of range
+    /// instead of saturating.
+    fn checked_add(self, dt: Self::Duration) -> Option<Self>;
+    fn is_valid(&self) -> bool;
+    /// Like [`is_valid`](Self::is_valid), but against `now` instead of the
+    /// current time.
+    fn is_valid_at(&self, now: &Self) -> bool;
+    fn is_expired(&self) -> bool;
+    /// Time left until `self`, `None` once it has been reached.
+    fn remaining(&self) -> Option<Self::Duration>;
+    /// Time passed since `self` was reached, `None` while it has not been.
+    fn elapsed_past(&self) -> Option<Self::Duration>;
+}
+
+/// A clock instance that can be passed around, for when the static
+/// [`TtlBackend::now`] is not enough, e.g. to inject a fake clock in tests.
+///
+/// See [`TimedOption::new_with_clock`] and the other `*_with` methods.
+pub trait Clock {
+    type Instant: TtlBackend;
+
+    fn now(&self) -> Self::Instant;
+}
+
+/// Backend durations that can be expressed as a whole number of
+/// milliseconds, negative durations count as zero.
+pub trait DurationMillis {
+    fn to_millis(&self) -> u64;
+    fn from_millis(millis: u64) -> Self;
+}
+
+impl DurationMillis for core::time::Duration {
+    #[inline]
+    fn to_millis(&self) -> u64 {
+        u64::try_from(self.as_millis()).unwrap_or(u64::MAX)
+    }
+
+    #[inline]
+    fn from_millis(millis: u64) -> Self {
+        core::time::Duration::from_millis(millis)
+    }
+}
+
+/// The integer backends are already in their own unit.
+impl DurationMillis for u64 {
+    #[inline]
+    fn to_millis(&self) -> u64 {
+        *self
+    }
+
+    #[inline]
+    fn from_millis(millis: u64) -> Self {
+        millis
+    }
+}
+
+impl DurationMillis for u32 {
+    #[inline]
+    fn to_millis(&self) -> u64 {
+        u64::from(*self)
+    }
+
+    #[inline]
+    fn from_millis(millis: u64) -> Self {
+        u32::try_from(millis).unwrap_or(u32::MAX)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl DurationMillis for chrono::Duration {
+    #[inline]
+    fn to_millis(&self) -> u64 {
+        u64::try_from(self.num_milliseconds()).unwrap_or(0)
+    }
+
+    #[inline]
+    fn from_millis(millis: u64) -> Self {
+        chrono::Duration::milliseconds(i64::try_from(millis).unwrap_or(i64::MAX))
+    }
+}
+
+#[cfg(feature = "coarsetime")]
+impl DurationMillis for coarsetime::Duration {
+    #[inline]
+    fn to_millis(&self) -> u64 {
+        self.as_millis()
+    }
+
+    #[inline]
+    fn from_millis(millis: u64) -> Self {
+        coarsetime::Duration::from_millis(millis)
+    }
+}
+
+/// Backend durations that convert to and from a [`core::time::Duration`],
+/// negative durations count as zero.
+pub trait DurationStd {
+    fn to_std(&self) -> core::time::Duration;
+    fn from_std(dt: core::time::Duration) -> Self;
+}
+
+impl DurationStd for core::time::Duration {
+    #[inline]
+    fn to_std(&self) -> core::time::Duration {
+        *self
+    }
+
+    #[inline]
+    fn from_std(dt: core::time::Duration) -> Self {
+        dt
+    }
+}
+
+/// The integer backends are read as milliseconds, like [`DurationMillis`],
+/// which also counts a [`Ticks`] ttl as that many milliseconds.
+impl DurationStd for u64 {
+    #[inline]
+    fn to_std(&self) -> core::time::Duration {
+        core::time::Duration::from_millis(*self)
+    }
+
+    #[inline]
+    fn from_std(dt: core::time::Duration) -> Self {
+        dt.to_millis()
+    }
+}
+
+impl DurationStd for u32 {
+    #[inline]
+    fn to_std(&self) -> core::time::Duration {
+        core::time::Duration::from_millis(u64::from(*self))
+    }
+
+    #[inline]
+    fn from_std(dt: core::time::Duration) -> Self {
+        u32::try_from(dt.to_millis()).unwrap_or(u32::MAX)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl DurationStd for chrono::Duration {
+    #[inline]
+    fn to_std(&self) -> core::time::Duration {
+        chrono::Duration::to_std(self).unwrap_or(core::time::Duration::ZERO)
+    }
+
+    #[inline]
+    fn from_std(dt: core::time::Duration) -> Self {
+        chrono::Duration::from_std(dt).unwrap_or(chrono::Duration::MAX)
+    }
+}
+
+#[cfg(feature = "coarsetime")]
+impl DurationStd for coarsetime::Duration {
+    #[inline]
+    fn to_std(&self) -> core::time::Duration {
+        core::time::Duration::from(*self)
+    }
+
+    #[inline]
+    fn from_std(dt: core::time::Duration) -> Self {
+        coarsetime::Duration::from(dt)
+    }
+}
+
+/// Ttls too large to represent saturate to the latest representable instant.
+#[cfg(feature = "std")]
+impl TtlBackend for std::time::Instant {
+    type Duration = std::time::Duration;
+
+    #[inline]
+    fn now() -> Self {
+        std::time::Instant::now()
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        let now = std::time::Instant::now();
+        now.checked_sub(std::time::Duration::from_secs(1))
+            .unwrap_or(now)
+    }
+
+    #[inline]
+    fn add(self, dt: Self::Duration) -> Self {
+        saturating_add(self, dt, std::time::Instant::checked_add)
+    }
+
+    #[inline]
+    fn checked_add(self, dt: Self::Duration) -> Option<Self> {
+        std::time::Instant::checked_add(&self, dt)
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        *self > std::time::Instant::now()
+    }
+
+    #[inline]
+    fn is_valid_at(&self, now: &Self) -> bool {
+        *self > *now
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        *self <= std::time::Instant::now()
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        let now = std::time::Instant::now();
+        match *self > now {
+            true => Some(*self - now),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        let now = std::time::Instant::now();
+        match *self <= now {
+            true => Some(now - *self),
+            false => None,
+        }
+    }
+}
+
+/// A wall-clock backend, unlike [`Instant`](std::time::Instant) it can be
+/// persisted. Ttls too large to represent saturate to the latest
+/// representable time.
+#[cfg(feature = "std")]
+impl TtlBackend for std::time::SystemTime {
+    type Duration = std::time::Duration;
+
+    #[inline]
+    fn now() -> Self {
+        std::time::SystemTime::now()
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        std::time::SystemTime::UNIX_EPOCH
+    }
+
+    #[inline]
+    fn add(self, dt: Self::Duration) -> Self {
+        saturating_add(self, dt, std::time::SystemTime::checked_add)
+    }
+
+    #[inline]
+    fn checked_add(self, dt: Self::Duration) -> Option<Self> {
+        std::time::SystemTime::checked_add(&self, dt)
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        *self > std::time::SystemTime::now()
+    }
+
+    #[inline]
+    fn is_valid_at(&self, now: &Self) -> bool {
+        *self > *now
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        *self <= std::time::SystemTime::now()
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        match self.duration_since(std::time::SystemTime::now()) {
+            Ok(remaining) if !remaining.is_zero() => Some(remaining),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        std::time::SystemTime::now().duration_since(*self).ok()
+    }
+}
+
+/// Ttls too large to represent saturate to `DateTime::MAX_UTC`.
+#[cfg(feature = "chrono")]
+impl TtlBackend for chrono::DateTime<chrono::Utc> {
+    type Duration = chrono::Duration;
+
+    #[inline]
+    fn now() -> Self {
+        chrono::Utc::now()
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        let now = chrono::Utc::now();
+        now.checked_sub_signed(chrono::Duration::seconds(1))
+            .unwrap_or(now)
+    }
+
+    #[inline]
+    fn add(self, dt: Self::Duration) -> Self {
+        match self.checked_add_signed(dt) {
+            Some(deadline) => deadline,
+            None if dt < chrono::Duration::zero() => chrono::DateTime::<chrono::Utc>::MIN_UTC,
+            None => chrono::DateTime::<chrono::Utc>::MAX_UTC,
+        }
+    }
+
+    #[inline]
+    fn checked_add(self, dt: Self::Duration) -> Option<Self> {
+        self.checked_add_signed(dt)
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        *self > chrono::Utc::now()
+    }
+
+    #[inline]
+    fn is_valid_at(&self, now: &Self) -> bool {
+        *self > *now
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        *self <= chrono::Utc::now()
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        let now = chrono::Utc::now();
+        match *self > now {
+            true => Some(*self - now),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        let now = chrono::Utc::now();
+        match *self <= now {
+            true => Some(now - *self),
+            false => None,
+        }
+    }
+}
+
+/// A wall-clock deadline in milliseconds since the unix epoch, with ttls in
+/// milliseconds. Unlike [`SystemTime`](std::time::SystemTime) it is a plain
+/// integer that is easy to store.
+#[cfg(feature = "std")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct EpochMillis(pub u64);
+
+#[cfg(feature = "std")]
+impl EpochMillis {
+    /// Milliseconds since the unix epoch, saturating on overflow.
+    #[inline]
+    fn now_millis() -> u64 {
+        let since_epoch = std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        u64::try_from(since_epoch.as_millis()).unwrap_or(u64::MAX)
+    }
+}
+
+#[cfg(feature = "std")]
+impl TtlBackend for EpochMillis {
+    type Duration = u64;
+
+    #[inline]
+    fn now() -> Self {
+        EpochMillis(EpochMillis::now_millis())
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        EpochMillis(0)
+    }
+
+    #[inline]
+    fn add(self, dt: Self::Duration) -> Self {
+        EpochMillis(self.0.saturating_add(dt))
+    }
+
+    #[inline]
+    fn checked_add(self, dt: Self::Duration) -> Option<Self> {
+        self.0.checked_add(dt).map(EpochMillis)
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        self.0 > EpochMillis::now_millis()
+    }
+
+    #[inline]
+    fn is_valid_at(&self, now: &Self) -> bool {
+        self.0 > now.0
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        self.0 <= EpochMillis::now_millis()
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        let now = EpochMillis::now_millis();
+        match self.0 > now {
+            true => Some(self.0 - now),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        let now = EpochMillis::now_millis();
+        match self.0 <= now {
+            true => Some(now - self.0),
+            false => None,
+        }
+    }
+}
+
+/// A deadline that is never reached, so one code path can handle both cached
+/// and permanent values. The only expired state is the one given by
+/// [`TtlBackend::expired`], i.e. `Eternal(false)`. The ttl is `()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Eternal(pub bool);
+
+impl TtlBackend for Eternal {
+    type Duration = ();
+
+    #[inline]
+    fn now() -> Self {
+        Eternal(true)
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        Eternal(false)
+    }
+
+    #[inline]
+    fn add(self, _dt: Self::Duration) -> Self {
+        self
+    }
+
+    #[inline]
+    fn checked_add(self, _dt: Self::Duration) -> Option<Self> {
+        Some(self)
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        self.0
+    }
+
+    #[inline]
+    fn is_valid_at(&self, _now: &Self) -> bool {
+        self.0
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        !self.0
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        match self.0 {
+            true => Some(()),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        match self.0 {
+            true => None,
+            false => Some(()),
+        }
+    }
+}
+
+/// A logical clock deadline for deterministic simulations and tests, with
+/// ttls counted in ticks. The clock is a process wide counter that only moves
+/// with [`advance_ticks`] and [`set_ticks`].
+///
+/// It needs no `std`, so on embedded targets it can follow a hardware counter
+/// by calling [`set_ticks`], e.g. from a timer interrupt. The counter is an
+/// `AtomicU64`, so `Ticks` only exists on targets with 64-bit atomics, use
+/// [`RelativeMillis`] on 32-bit targets like `thumbv7em`.
+///
+/// Its `u64` ttl goes through [`DurationMillis`] and [`DurationStd`] as
+/// milliseconds, so the remaining-time serde formats like
+/// `as_remaining_millis` write one tick as one millisecond, and a reader on
+/// a wall clock backend reads it as such.
+#[cfg(target_has_atomic = "64")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Ticks(pub u64);
+
+#[cfg(target_has_atomic = "64")]
+static TICKS: core::sync::atomic::AtomicU64 = core::sync::atomic::AtomicU64::new(0);
+
+/// Moves the [`Ticks`] clock forward by `n` ticks.
+#[cfg(target_has_atomic = "64")]
+#[inline]
+pub fn advance_ticks(n: u64) {
+    TICKS.fetch_add(n, core::sync::atomic::Ordering::AcqRel);
+}
+
+/// Sets the [`Ticks`] clock to `now`, which should never move backwards.
+#[cfg(target_has_atomic = "64")]
+#[inline]
+pub fn set_ticks(now: u64) {
+    TICKS.store(now, core::sync::atomic::Ordering::Release);
+}
+
+#[cfg(target_has_atomic = "64")]
+impl Ticks {
+    /// Current value of the tick counter.
+    #[inline]
+    fn current() -> u64 {
+        TICKS.load(core::sync::atomic::Ordering::Acquire)
+    }
+}
+
+/// Ticks between two readings, saturating at zero.
+#[cfg(target_has_atomic = "64")]
+impl core::ops::Sub for Ticks {
+    type Output = u64;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.0.saturating_sub(rhs.0)
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl TtlBackend for Ticks {
+    type Duration = u64;
+
+    #[inline]
+    fn now() -> Self {
+        Ticks(Ticks::current())
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        Ticks(0)
+    }
+
+    #[inline]
+    fn add(self, dt: Self::Duration) -> Self {
+        Ticks(self.0.saturating_add(dt))
+    }
+
+    #[inline]
+    fn checked_add(self, dt: Self::Duration) -> Option<Self> {
+        self.0.checked_add(dt).map(Ticks)
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        self.0 > Ticks::current()
+    }
+
+    #[inline]
+    fn is_valid_at(&self, now: &Self) -> bool {
+        self.0 > now.0
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        self.0 <= Ticks::current()
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        let now = Ticks::current();
+        match self.0 > now {
+            true => Some(self.0 - now),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        let now = Ticks::current();
+        match self.0 <= now {
+            true => Some(now - self.0),
+            false => None,
+        }
+    }
+}
+
+/// A deadline in milliseconds of a wrapping 32 bit counter, e.g. the time
+/// since boot on embedded systems. The counter is process wide and follows
+/// the value given to [`set_relative_now`].
+///
+/// Deadlines are compared with wrapping arithmetic, so expiry keeps working
+/// when the counter wraps around after about 49.7 days. A ttl is capped to
+/// `i32::MAX` milliseconds (about 24.8 days) and a deadline that long in the
+/// past reads as valid again.
+#[cfg(target_has_atomic = "32")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RelativeMillis(pub u32);
+
+#[cfg(target_has_atomic = "32")]
+static RELATIVE_NOW: core::sync::atomic::AtomicU32 = core::sync::atomic::AtomicU32::new(0);
+
+/// Sets the [`RelativeMillis`] clock to `now` milliseconds, it may wrap.
+#[cfg(target_has_atomic = "32")]
+#[inline]
+pub fn set_relative_now(now: u32) {
+    RELATIVE_NOW.store(now, core::sync::atomic::Ordering::Release);
+}
+
+#[cfg(target_has_atomic = "32")]
+impl RelativeMillis {
+    /// Current value of the counter.
+    #[inline]
+    fn current() -> u32 {
+        RELATIVE_NOW.load(core::sync::atomic::Ordering::Acquire)
+    }
+
+    /// Signed distance from now to `self`, positive while it is ahead.
+    #[inline]
+    fn ahead_of_now(&self) -> i32 {
+        self.0.wrapping_sub(RelativeMillis::current()) as i32
+    }
+}
+
+#[cfg(target_has_atomic = "32")]
+impl TtlBackend for RelativeMillis {
+    type Duration = u32;
+
+    #[inline]
+    fn now() -> Self {
+        RelativeMillis(RelativeMillis::current())
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        RelativeMillis(RelativeMillis::current().wrapping_sub(1))
+    }
+
+    #[inline]
+    fn add(self, dt: Self::Duration) -> Self {
+        RelativeMillis(self.0.wrapping_add(dt.min(i32::MAX as u32)))
+    }
+
+    #[inline]
+    fn checked_add(self, dt: Self::Duration) -> Option<Self> {
+        match dt <= i32::MAX as u32 {
+            true => Some(RelativeMillis(self.0.wrapping_add(dt))),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        self.ahead_of_now() > 0
+    }
+
+    #[inline]
+    fn is_valid_at(&self, now: &Self) -> bool {
+        self.0.wrapping_sub(now.0) as i32 > 0
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        self.ahead_of_now() <= 0
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        let ahead = self.ahead_of_now();
+        match ahead > 0 {
+            true => Some(ahead as u32),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        let ahead = self.ahead_of_now();
+        match ahead <= 0 {
+            true => Some(ahead.unsigned_abs()),
+            false => None,
+        }
+    }
+}
+
+/// A mock clock deadline for tests, the clock only moves when it is set or
+/// advanced so expiry is fully deterministic.
+///
+/// The clock is thread-local, each test thread starts at zero and tests
+/// running in parallel do not interfere with each other.
+///
+/// ```
+/// use std::time::Duration;
+/// use timed_option::{MockClock, TimedOption};
+///
+/// let token = TimedOption::<_, MockClock>::new("token", Duration::from_secs(60));
+/// MockClock::advance(Duration::from_secs(59));
+/// assert!(token.is_some());
+/// MockClock::advance(Duration::from_secs(1));
+/// assert!(token.is_none());
+/// ```
+#[cfg(feature = "mock")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct MockClock(pub std::time::Duration);
+
+#[cfg(feature = "mock")]
+std::thread_local! {
+    static MOCK_NOW: std::cell::Cell<std::time::Duration> =
+        const { std::cell::Cell::new(std::time::Duration::ZERO) };
+}
+
+#[cfg(feature = "mock")]
+impl MockClock {
+    /// Sets the current thread's mock clock to `now`.
+    #[inline]
+    pub fn set_now(now: std::time::Duration) {
+        MOCK_NOW.with(|cell| cell.set(now));
+    }
+
+    /// Moves the current thread's mock clock forward by `dt`.
+    #[inline]
+    pub fn advance(dt: std::time::Duration) {
+        MOCK_NOW.with(|cell| cell.set(cell.get().saturating_add(dt)));
+    }
+
+    /// Current value of the current thread's mock clock.
+    #[inline]
+    fn current() -> std::time::Duration {
+        MOCK_NOW.with(std::cell::Cell::get)
+    }
+}
+
+/// Time between two mock clock readings, saturating at zero.
+#[cfg(feature = "mock")]
+impl core::ops::Sub for MockClock {
+    type Output = std::time::Duration;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.0.saturating_sub(rhs.0)
+    }
+}
+
+#[cfg(feature = "mock")]
+impl TtlBackend for MockClock {
+    type Duration = std::time::Duration;
+
+    #[inline]
+    fn now() -> Self {
+        MockClock(MockClock::current())
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        MockClock(std::time::Duration::ZERO)
+    }
+
+    #[inline]
+    fn add(self, dt: Self::Duration) -> Self {
+        MockClock(self.0.saturating_add(dt))
+    }
+
+    #[inline]
+    fn checked_add(self, dt: Self::Duration) -> Option<Self> {
+        self.0.checked_add(dt).map(MockClock)
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        self.0 > MockClock::current()
+    }
+
+    #[inline]
+    fn is_valid_at(&self, now: &Self) -> bool {
+        self.0 > now.0
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        self.0 <= MockClock::current()
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        let now = MockClock::current();
+        match self.0 > now {
+            true => Some(self.0 - now),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        let now = MockClock::current();
+        match self.0 <= now {
+            true => Some(now - self.0),
+            false => None,
+        }
+    }
+}
+
+/// Follows the tokio clock, so `tokio::time::pause` and `tokio::time::advance`
+/// also drive expiry.
+#[cfg(feature = "tokio")]
+impl TtlBackend for tokio::time::Instant {
+    type Duration = std::time::Duration;
+
+    #[inline]
+    fn now() -> Self {
+        tokio::time::Instant::now()
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        let now = tokio::time::Instant::now();
+        now.checked_sub(std::time::Duration::from_secs(1))
+            .unwrap_or(now)
+    }
+
+    #[inline]
+    fn add(self, dt: Self::Duration) -> Self {
+        saturating_add(self, dt, tokio::time::Instant::checked_add)
+    }
+
+    #[inline]
+    fn checked_add(self, dt: Self::Duration) -> Option<Self> {
+        tokio::time::Instant::checked_add(&self, dt)
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        *self > tokio::time::Instant::now()
+    }
+
+    #[inline]
+    fn is_valid_at(&self, now: &Self) -> bool {
+        *self > *now
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        *self <= tokio::time::Instant::now()
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        let now = tokio::time::Instant::now();
+        match *self > now {
+            true => Some(*self - now),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        let now = tokio::time::Instant::now();
+        match *self <= now {
+            true => Some(now - *self),
+            false => None,
+        }
+    }
+}
+
+/// Reads the TSC through quanta's lazily calibrated global clock, so no
+/// `Clock` has to be threaded through the static [`TtlBackend::now`]. The
+/// first read pays for the calibration, every later read is much cheaper than
+/// [`Instant::now`](std::time::Instant::now).
+#[cfg(feature = "quanta")]
+impl TtlBackend for quanta::Instant {
+    type Duration = std::time::Duration;
+
+    #[inline]
+    fn now() -> Self {
+        quanta::Instant::now()
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        let now = quanta::Instant::now();
+        now.checked_sub(std::time::Duration::from_secs(1))
+            .unwrap_or(now)
+    }
+
+    #[inline]
+    fn add(self, dt: Self::Duration) -> Self {
+        saturating_add(self, dt, quanta::Instant::checked_add)
+    }
+
+    #[inline]
+    fn checked_add(self, dt: Self::Duration) -> Option<Self> {
+        quanta::Instant::checked_add(&self, dt)
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        *self > quanta::Instant::now()
+    }
+
+    #[inline]
+    fn is_valid_at(&self, now: &Self) -> bool {
+        *self > *now
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        *self <= quanta::Instant::now()
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        let now = quanta::Instant::now();
+        match *self > now {
+            true => Some(*self - now),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        let now = quanta::Instant::now();
+        match *self <= now {
+            true => Some(now - *self),
+            false => None,
+        }
+    }
+}
+
+/// Reads the cached coarse clock, which only moves forward when it is
+/// updated. Call `coarsetime::Instant::update()` periodically, e.g. from an
+/// event loop, or run a `coarsetime::Updater`, otherwise values never expire.
+#[cfg(feature = "coarsetime")]
+impl TtlBackend for coarsetime::Instant {
+    type Duration = coarsetime::Duration;
+
+    #[inline]
+    fn now() -> Self {
+        coarsetime::Instant::recent()
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        coarsetime::Instant::recent().saturating_sub(coarsetime::Duration::from_secs(1))
+    }
+
+    #[inline]
+    fn add(self, dt: Self::Duration) -> Self {
+        self.saturating_add(dt)
+    }
+
+    #[inline]
+    fn checked_add(self, dt: Self::Duration) -> Option<Self> {
+        coarsetime::Instant::checked_add(self, dt)
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        *self > coarsetime::Instant::recent()
+    }
+
+    #[inline]
+    fn is_valid_at(&self, now: &Self) -> bool {
+        *self > *now
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        *self <= coarsetime::Instant::recent()
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        let now = coarsetime::Instant::recent();
+        match *self > now {
+            true => Some(*self - now),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        let now = coarsetime::Instant::recent();
+        match *self <= now {
+            true => Some(now - *self),
+            false => None,
+        }
+    }
+}
+
+/// Mirrors the [`Instant`](std::time::Instant) backend for browsers, where
+/// `std::time::Instant` is unavailable. On every other target
+/// `web_time::Instant` is `std::time::Instant` itself.
+#[cfg(all(feature = "web-time", target_family = "wasm", target_os = "unknown"))]
+impl TtlBackend for web_time::Instant {
+    type Duration = std::time::Duration;
+
+    #[inline]
+    fn now() -> Self {
+        web_time::Instant::now()
+    }
+
+    #[inline]
+    fn expired() -> Self {
+        let now = web_time::Instant::now();
+        now.checked_sub(std::time::Duration::from_secs(1))
+            .unwrap_or(now)
+    }
+
+    #[inline]
+    fn add(self, dt: Self::Duration) -> Self {
+        saturating_add(self, dt, web_time::Instant::checked_add)
+    }
+
+    #[inline]
+    fn checked_add(self, dt: Self::Duration) -> Option<Self> {
+        web_time::Instant::checked_add(&self, dt)
+    }
+
+    #[inline]
+    fn is_valid(&self) -> bool {
+        *self > web_time::Instant::now()
+    }
+
+    #[inline]
+    fn is_valid_at(&self, now: &Self) -> bool {
+        *self > *now
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        *self <= web_time::Instant::now()
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<Self::Duration> {
+        let now = web_time::Instant::now();
+        match *self > now {
+            true => Some(*self - now),
+            false => None,
+        }
+    }
+
+    #[inline]
+    fn elapsed_past(&self) -> Option<Self::Duration> {
+        let now = web_time::Instant::now();
+        match *self <= now {
+            true => Some(now - *self),
+            false => None,
+        }
+    }
+}
+
+/// Adds `dt` to `instant`, saturating to the latest instant `checked_add` can
+/// represent instead of overflowing.
+#[cfg(feature = "std")]
+fn saturating_add<I, F>(instant: I, dt: core::time::Duration, checked_add: F) -> I
+where
+    I: Copy,
+    F: Fn(&I, core::time::Duration) -> Option<I>,
+{
+    if let Some(sum) = checked_add(&instant, dt) {
+        return sum;
+    }
+    let mut instant = instant;
+    let mut step = dt;
+    while !step.is_zero() {
+        match checked_add(&instant, step) {
+            Some(sum) => instant = sum,
+            None => step /= 2,
+        }
+    }
+    instant
+}
+
+/// Shifts `ttl` by an offset sampled uniformly from `-jitter..=jitter`,
+/// clamped to zero.
+#[cfg(feature = "rand")]
+fn jittered(ttl: core::time::Duration, jitter: core::time::Duration) -> core::time::Duration {
+    let offset = jitter.as_secs_f64() * rand::random_range(-1.0..=1.0);
+    let ttl = (ttl.as_secs_f64() + offset).max(0.0);
+    core::time::Duration::try_from_secs_f64(ttl).unwrap_or(core::time::Duration::MAX)
 }
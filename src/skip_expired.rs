@@ -0,0 +1,74 @@
+//! Serializes a [`TimedOption`] only while its value is valid, so stale
+//! entries are not persisted.
+//!
+//! A valid timed-option is written as `{ "value": .., "remaining": .. }` with
+//! the remaining ttl in whole milliseconds, like
+//! [`as_remaining_millis`](crate::as_remaining_millis), an expired or `None`
+//! one is written as `null`. Deserializing re-stamps the deadline as
+//! `now() + remaining` and `null` gives an [empty](TimedOption::empty)
+//! timed-option.
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! use std::time::Instant;
+//! use timed_option::TimedOption;
+//!
+//! # #[cfg(feature = "std")]
+//! #[derive(Serialize, Deserialize)]
+//! struct Cache {
+//!     #[serde(with = "timed_option::skip_expired")]
+//!     entry: TimedOption<String, Instant>,
+//! }
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{DurationMillis, TimedOption, TtlBackend};
+
+#[derive(Serialize)]
+struct ValidRef<'a, T> {
+    value: &'a T,
+    remaining: u64,
+}
+
+#[derive(Deserialize)]
+struct Valid<T> {
+    value: T,
+    remaining: u64,
+}
+
+/// Serializes a valid timed-option with its remaining ttl in milliseconds,
+/// anything else as `null`.
+pub fn serialize<T, B, S>(option: &TimedOption<T, B>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    B: TtlBackend,
+    B::Duration: DurationMillis,
+    S: Serializer,
+{
+    match (option.as_option(), option.ttl.remaining()) {
+        (Some(value), Some(dt)) => serializer.serialize_some(&ValidRef {
+            value,
+            remaining: dt.to_millis(),
+        }),
+        _ => serializer.serialize_none(),
+    }
+}
+
+/// Deserializes a timed-option, re-stamping the ttl from the remaining
+/// milliseconds, `null` becomes an empty timed-option.
+pub fn deserialize<'de, T, B, D>(deserializer: D) -> Result<TimedOption<T, B>, D::Error>
+where
+    T: Deserialize<'de>,
+    B: TtlBackend,
+    B::Duration: DurationMillis,
+    D: Deserializer<'de>,
+{
+    Option::<Valid<T>>::deserialize(deserializer).map(|valid| match valid {
+        Some(Valid { value, remaining }) if remaining > 0 => {
+            TimedOption::new(value, B::Duration::from_millis(remaining))
+        }
+        Some(Valid { value, .. }) => TimedOption::new_expired(value),
+        None => TimedOption::empty(),
+    })
+}
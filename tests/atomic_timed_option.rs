@@ -0,0 +1,52 @@
+#![cfg(feature = "arc-swap")]
+
+use std::time::{Duration, Instant};
+
+use timed_option::{AtomicTimedOption, TimedOption, TimedValue};
+
+const TTL: Duration = Duration::from_secs(3500);
+
+#[test]
+fn atomic_timed_option_load_store() {
+    let token = AtomicTimedOption::<_, Instant>::empty();
+    assert_eq!(token.load(), TimedValue::None);
+
+    token.store("space_patato", TTL);
+    assert_eq!(token.load(), TimedValue::Valid("space_patato"));
+
+    token.store("space_patato", Duration::ZERO);
+    assert_eq!(token.load(), TimedValue::Expired("space_patato"));
+
+    token.clear();
+    assert_eq!(token.load(), TimedValue::None);
+
+    let token: AtomicTimedOption<_, Instant> = TimedOption::new(5, TTL).into();
+    assert_eq!(token.load(), TimedValue::Valid(5));
+}
+
+#[test]
+fn atomic_timed_option_readers_and_writer() {
+    let token = AtomicTimedOption::<_, Instant>::new((0u64, 0u64), TTL);
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let mut last = 0;
+                for _ in 0..10_000 {
+                    // both halves are always written together
+                    let (a, b) = token.load().unwrap_valid();
+                    assert_eq!(a, b);
+                    assert!(a >= last);
+                    last = a;
+                }
+            });
+        }
+        scope.spawn(|| {
+            for i in 1..=1_000 {
+                token.store((i, i), TTL);
+            }
+        });
+    });
+
+    assert_eq!(token.load(), TimedValue::Valid((1_000, 1_000)));
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, Duration, Utc};
+
+use timed_option::{TimedOption, TimedValue, TtlBackend};
+
+#[test]
+fn chrono_with_deadline() {
+    let exp = DateTime::parse_from_rfc3339("2000-01-01T00:00:00Z")
+        .unwrap()
+        .with_timezone(&Utc);
+    let token = TimedOption::with_deadline("jwt", exp);
+    assert_eq!(token.into_timed_value(), TimedValue::Expired("jwt"));
+
+    let exp = Utc::now() + Duration::hours(1);
+    let token = TimedOption::with_deadline("jwt", exp);
+    assert_eq!(*token.expires_at(), exp);
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("jwt"));
+}
+
+#[test]
+fn chrono_empty_is_expired() {
+    let empty = TimedOption::<&str, DateTime<Utc>>::empty();
+    assert!(*empty.expires_at() < Utc::now());
+    assert!(empty.is_none());
+
+    let token = TimedOption::<_, DateTime<Utc>>::new_expired("jwt");
+    assert!(*token.expires_at() < Utc::now());
+    assert!(token.is_expired());
+}
+
+#[test]
+fn chrono_zero_ttl() {
+    let token = TimedOption::<_, DateTime<Utc>>::new("jwt", Duration::zero());
+    assert!(token.is_none());
+    assert!(token.is_expired());
+    assert_eq!(token.remaining(), None);
+
+    let deadline = Utc::now();
+    let token = TimedOption::with_deadline("jwt", deadline);
+    assert!(token.is_expired());
+}
+
+#[test]
+fn chrono_saturating_ttl() {
+    let token = TimedOption::<_, DateTime<Utc>>::new("forever", Duration::MAX);
+    assert_eq!(*token.expires_at(), DateTime::<Utc>::MAX_UTC);
+    assert!(token.is_some());
+
+    let token = TimedOption::<_, DateTime<Utc>>::new("never", Duration::MIN);
+    assert_eq!(*token.expires_at(), DateTime::<Utc>::MIN_UTC);
+    assert!(token.is_expired());
+}
+
+#[test]
+fn chrono_remaining_and_elapsed_past() {
+    let ahead = Utc::now() + Duration::seconds(3500);
+    assert!(ahead.remaining().unwrap() <= Duration::seconds(3500));
+    assert_eq!(ahead.elapsed_past(), None);
+
+    let behind = Utc::now() - Duration::seconds(1);
+    assert_eq!(behind.remaining(), None);
+    assert!(behind.elapsed_past().unwrap() >= Duration::seconds(1));
+}
+
+#[test]
+fn chrono_convert_from_instant() {
+    let token =
+        TimedOption::<_, std::time::Instant>::new("jwt", std::time::Duration::from_secs(3500));
+    let token: TimedOption<_, DateTime<Utc>> = token.convert_backend(|deadline| {
+        let remaining = deadline.remaining().unwrap_or_default();
+        Utc::now() + Duration::from_std(remaining).unwrap()
+    });
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("jwt"));
+    assert!(*token.expires_at() <= Utc::now() + Duration::seconds(3500));
+    assert!(*token.expires_at() > Utc::now() + Duration::seconds(3499));
+
+    let token = TimedOption::<_, std::time::Instant>::new_expired("jwt");
+    let token: TimedOption<_, DateTime<Utc>> =
+        token.convert_backend(|deadline| match deadline.remaining() {
+            Some(remaining) => Utc::now() + Duration::from_std(remaining).unwrap(),
+            None => DateTime::<Utc>::expired(),
+        });
+    assert_eq!(token.into_timed_value(), TimedValue::Expired("jwt"));
+}
+
+#[test]
+fn chrono_checked_new() {
+    assert!(TimedOption::<_, DateTime<Utc>>::checked_new("jwt", Duration::seconds(60)).is_some());
+    assert!(TimedOption::<_, DateTime<Utc>>::checked_new("jwt", Duration::MAX).is_none());
+    assert!(TimedOption::<_, DateTime<Utc>>::checked_new("jwt", Duration::MIN).is_none());
+}
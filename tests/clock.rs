@@ -0,0 +1,109 @@
+#![cfg(feature = "std")]
+
+use std::cell::Cell;
+
+use timed_option::{Clock, EpochMillis, Eternal, RelativeMillis, TimedOption, TimedValue};
+
+struct FakeClock(Cell<u64>);
+
+impl FakeClock {
+    fn advance(&self, ms: u64) {
+        self.0.set(self.0.get() + ms);
+    }
+}
+
+impl Clock for FakeClock {
+    type Instant = EpochMillis;
+
+    fn now(&self) -> EpochMillis {
+        EpochMillis(self.0.get())
+    }
+}
+
+struct EternalClock;
+
+impl Clock for EternalClock {
+    type Instant = Eternal;
+
+    fn now(&self) -> Eternal {
+        Eternal(true)
+    }
+}
+
+struct RelativeClock(Cell<u32>);
+
+impl Clock for RelativeClock {
+    type Instant = RelativeMillis;
+
+    fn now(&self) -> RelativeMillis {
+        RelativeMillis(self.0.get())
+    }
+}
+
+#[test]
+fn injected_clocks() {
+    let here = FakeClock(Cell::new(1_000));
+    let there = FakeClock(Cell::new(5_000));
+
+    let token = TimedOption::new_with_clock(&here, "space_patato", 100);
+    assert_eq!(*token.expires_at(), EpochMillis(1_100));
+    assert!(token.is_some_with(&here));
+    assert!(token.is_none_with(&there));
+
+    here.advance(99);
+    assert_eq!(token.into_option_with(&here), Some("space_patato"));
+    here.advance(1);
+    assert_eq!(
+        token.into_timed_value_with(&here),
+        TimedValue::Expired("space_patato")
+    );
+
+    let other = TimedOption::new_with_clock(&there, "space_tomato", 100);
+    assert_eq!(
+        other.into_timed_value_with(&there),
+        TimedValue::Valid("space_tomato")
+    );
+    assert_eq!(
+        other.into_timed_value_with(&here),
+        TimedValue::Valid("space_tomato")
+    );
+
+    let empty = TimedOption::<&str, EpochMillis>::empty();
+    assert_eq!(empty.into_timed_value_with(&here), TimedValue::None);
+}
+
+#[test]
+fn injected_eternal_clock() {
+    let token = TimedOption::new_with_clock(&EternalClock, "space_patato", ());
+    assert!(token.is_some_with(&EternalClock));
+    assert_eq!(token.into_option_with(&EternalClock), Some("space_patato"));
+    assert_eq!(
+        token.into_timed_value_with(&EternalClock),
+        TimedValue::Valid("space_patato")
+    );
+
+    let expired = TimedOption::<_, Eternal>::new_expired("space_tomato");
+    assert!(expired.is_none_with(&EternalClock));
+    assert_eq!(
+        expired.into_timed_value_with(&EternalClock),
+        TimedValue::Expired("space_tomato")
+    );
+}
+
+#[test]
+fn injected_relative_clock_wraps() {
+    let clock = RelativeClock(Cell::new(u32::MAX - 10));
+    let token = TimedOption::new_with_clock(&clock, "space_patato", 100);
+    assert_eq!(*token.expires_at(), RelativeMillis(89));
+    assert!(token.is_some_with(&clock));
+
+    clock.0.set(88);
+    assert_eq!(token.into_option_with(&clock), Some("space_patato"));
+
+    clock.0.set(89);
+    assert!(token.is_none_with(&clock));
+    assert_eq!(
+        token.into_timed_value_with(&clock),
+        TimedValue::Expired("space_patato")
+    );
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "coarsetime")]
+
+use coarsetime::{Duration, Instant};
+
+use timed_option::{TimedOption, TimedValue, TtlBackend};
+
+// a single test since the coarse clock is shared by the whole test binary
+#[test]
+fn coarsetime_instant_backend() {
+    let ttl = Duration::from_millis(50);
+    Instant::update();
+    let token = TimedOption::<_, Instant>::new("space_patato", ttl);
+    assert!(token.is_some());
+
+    // the coarse clock only moves on update
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+
+    Instant::update();
+    assert!(token.is_none());
+    assert_eq!(token.remaining(), None);
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+    assert!(TimedOption::<&str, Instant>::empty().is_none());
+
+    let ahead = Instant::recent() + Duration::from_secs(3500);
+    assert_eq!(ahead.remaining(), Some(Duration::from_secs(3500)));
+    assert_eq!(ahead.elapsed_past(), None);
+
+    let behind = Instant::recent();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    Instant::update();
+    assert_eq!(behind.remaining(), None);
+    assert!(behind.elapsed_past().unwrap() > Duration::from_millis(0));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "std")]
+
+use std::mem::size_of;
+use std::num::NonZeroU32;
+use std::time::{Duration, Instant};
+
+use timed_option::{CompactTimedOption, TimedOption, TimedValue};
+
+const TTL: Duration = Duration::from_secs(3500);
+
+#[test]
+fn compact_timed_option_size() {
+    assert_eq!(
+        size_of::<CompactTimedOption<NonZeroU32, Instant>>(),
+        size_of::<(NonZeroU32, Instant)>()
+    );
+
+    // without a niche in T the option discriminant is saved
+    assert_eq!(
+        size_of::<CompactTimedOption<u64, Instant>>(),
+        size_of::<(u64, Instant)>()
+    );
+    assert!(size_of::<CompactTimedOption<u64, Instant>>() < size_of::<TimedOption<u64, Instant>>());
+}
+
+#[test]
+fn compact_timed_option_states() {
+    let token = CompactTimedOption::<_, Instant>::new("space_patato", TTL);
+    assert!(token.is_some());
+    assert_eq!(token.as_option(), Some(&"space_patato"));
+
+    let token = CompactTimedOption::<_, Instant>::with_deadline("space_patato", Instant::now());
+    assert!(token.is_none());
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+
+    let token = CompactTimedOption::<&str, Instant>::empty();
+    assert_eq!(token.expires_at(), None);
+    assert_eq!(token.into_timed_value(), TimedValue::None);
+}
+
+#[test]
+fn compact_timed_option_conversion() {
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    let compact: CompactTimedOption<_, _> = token.into();
+    assert_eq!(compact.expires_at(), Some(token.expires_at()));
+
+    let token: TimedOption<_, _> = compact.into();
+    assert_eq!(token, TimedValue::Valid("space_patato"));
+
+    let compact: CompactTimedOption<_, _> = TimedOption::<u8, Instant>::empty().into();
+    assert_eq!(compact.as_timed_value(), TimedValue::None);
+}
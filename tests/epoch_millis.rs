@@ -0,0 +1,65 @@
+#![cfg(feature = "std")]
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use timed_option::{EpochMillis, TimedOption, TimedValue, TtlBackend};
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+#[test]
+fn epoch_millis_backend() {
+    let mut token = TimedOption::<_, EpochMillis>::new("space_patato", 3_500_000);
+    assert!(token.is_some());
+    assert!(token.remaining().unwrap() <= 3_500_000);
+
+    token.expire();
+    assert_eq!(*token.expires_at(), EpochMillis(0));
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+}
+
+#[test]
+fn epoch_millis_boundary() {
+    let now = now_millis();
+    let at_deadline = TimedOption::with_deadline("space_patato", EpochMillis(now));
+    assert!(at_deadline.is_none());
+    assert_eq!(at_deadline.remaining(), None);
+
+    let later = TimedOption::with_deadline("space_patato", EpochMillis(now + 60_000));
+    assert!(later.is_some());
+}
+
+#[test]
+fn epoch_millis_overflow() {
+    let token = TimedOption::<_, EpochMillis>::new("forever", u64::MAX);
+    assert_eq!(*token.expires_at(), EpochMillis(u64::MAX));
+    assert!(token.is_some());
+
+    let mut token = TimedOption::with_deadline("forever", EpochMillis(u64::MAX - 1));
+    token.extend(10);
+    assert_eq!(*token.expires_at(), EpochMillis(u64::MAX));
+}
+
+#[test]
+fn epoch_millis_remaining_and_elapsed_past() {
+    let ahead = EpochMillis(now_millis() + 60_000);
+    assert!(ahead.remaining().unwrap() <= 60_000);
+    assert_eq!(ahead.elapsed_past(), None);
+
+    let behind = EpochMillis(now_millis() - 1_000);
+    assert_eq!(behind.remaining(), None);
+    assert!(behind.elapsed_past().unwrap() >= 1_000);
+}
+
+#[test]
+fn epoch_millis_checked_new() {
+    assert!(TimedOption::<_, EpochMillis>::checked_new("space_patato", 60_000).is_some());
+    assert!(TimedOption::<_, EpochMillis>::checked_new("space_patato", u64::MAX).is_none());
+}
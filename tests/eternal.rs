@@ -0,0 +1,30 @@
+use timed_option::{Eternal, TimedOption, TimedValue, TtlBackend};
+
+#[test]
+fn eternal_backend() {
+    let mut token = TimedOption::<_, Eternal>::new("space_patato", ());
+    assert!(token.is_some());
+    assert_eq!(token.remaining(), Some(()));
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    token.extend(());
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+
+    token.expire();
+    assert!(token.is_expired());
+    assert_eq!(token.remaining(), None);
+
+    token.refresh(());
+    assert!(token.is_some());
+
+    assert!(TimedOption::<&str, Eternal>::empty().is_none());
+}
+
+#[test]
+fn eternal_remaining_and_elapsed_past() {
+    assert_eq!(Eternal(true).remaining(), Some(()));
+    assert_eq!(Eternal(true).elapsed_past(), None);
+
+    assert_eq!(Eternal(false).remaining(), None);
+    assert_eq!(Eternal(false).elapsed_past(), Some(()));
+}
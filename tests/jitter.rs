@@ -0,0 +1,40 @@
+#![cfg(all(feature = "rand", feature = "mock"))]
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use timed_option::{MockClock, TimedOption};
+
+#[test]
+fn timed_option_new_jittered() {
+    MockClock::set_now(Duration::from_secs(1_000));
+    let ttl = Duration::from_secs(60);
+    let jitter = Duration::from_secs(10);
+
+    let deadlines: Vec<_> = (0..1_000)
+        .map(|_| TimedOption::<_, MockClock>::new_jittered((), ttl, jitter))
+        .map(|token| token.expires_at().0)
+        .collect();
+
+    let earliest = Duration::from_secs(1_050);
+    let latest = Duration::from_secs(1_070);
+    assert!(deadlines.iter().all(|d| (earliest..=latest).contains(d)));
+
+    // spread over both sides of the ttl
+    let distinct: HashSet<_> = deadlines.iter().collect();
+    assert!(distinct.len() > 900);
+    assert!(deadlines.iter().any(|d| *d < Duration::from_secs(1_055)));
+    assert!(deadlines.iter().any(|d| *d > Duration::from_secs(1_065)));
+    let mean = deadlines.iter().sum::<Duration>() / deadlines.len() as u32;
+    assert!(mean > Duration::from_secs(1_058) && mean < Duration::from_secs(1_062));
+}
+
+#[test]
+fn timed_option_new_jittered_clamps_to_zero() {
+    MockClock::set_now(Duration::from_secs(1_000));
+    for _ in 0..100 {
+        let token =
+            TimedOption::<_, MockClock>::new_jittered((), Duration::ZERO, Duration::from_secs(1));
+        assert!(token.expires_at().0 >= Duration::from_secs(1_000));
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "mock")]
+
+use std::time::Duration;
+
+use timed_option::{MockClock, TimedOption, TimedValue, TtlBackend};
+
+#[test]
+fn mock_clock_backend() {
+    let ttl = Duration::from_secs(3500);
+    let token = TimedOption::<_, MockClock>::new("space_patato", ttl);
+    assert!(token.is_some());
+
+    MockClock::advance(ttl - Duration::from_secs(1));
+    assert_eq!(token.remaining(), Some(Duration::from_secs(1)));
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+
+    MockClock::advance(Duration::from_secs(1));
+    assert!(token.is_none());
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+}
+
+#[test]
+fn mock_clock_set_now() {
+    MockClock::set_now(Duration::from_secs(100));
+    let token = TimedOption::<_, MockClock>::new("space_patato", Duration::from_secs(10));
+    assert_eq!(*token.expires_at(), MockClock(Duration::from_secs(110)));
+
+    MockClock::set_now(Duration::from_secs(110));
+    assert!(token.is_none());
+
+    // rewinding the clock makes the value valid again
+    MockClock::set_now(Duration::from_secs(105));
+    assert!(token.is_some());
+}
+
+#[test]
+fn mock_clock_is_thread_local() {
+    MockClock::advance(Duration::from_secs(60));
+    std::thread::spawn(|| {
+        let token = TimedOption::<_, MockClock>::new("space_patato", Duration::from_secs(1));
+        assert_eq!(*token.expires_at(), MockClock(Duration::from_secs(1)));
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn mock_clock_remaining_and_elapsed_past() {
+    MockClock::set_now(Duration::from_secs(100));
+    let deadline = MockClock(Duration::from_secs(110));
+    assert_eq!(deadline.remaining(), Some(Duration::from_secs(10)));
+    assert_eq!(deadline.elapsed_past(), None);
+
+    MockClock::set_now(Duration::from_secs(110));
+    assert_eq!(deadline.remaining(), None);
+    assert_eq!(deadline.elapsed_past(), Some(Duration::ZERO));
+
+    MockClock::set_now(Duration::from_secs(125));
+    assert_eq!(deadline.elapsed_past(), Some(Duration::from_secs(15)));
+}
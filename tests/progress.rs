@@ -0,0 +1,50 @@
+use timed_option::{advance_ticks, Ticks, TimedOptionAged};
+
+// a single test since the tick counter is shared by the whole test binary
+#[test]
+fn timed_option_aged_progress() {
+    let mut token = TimedOptionAged::<_, Ticks>::new("space_patato", 10);
+    assert_eq!(token.progress(), Some(0.0));
+
+    advance_ticks(5);
+    assert_eq!(token.progress(), Some(0.5));
+
+    advance_ticks(5);
+    assert_eq!(token.progress(), Some(1.0));
+
+    // clamped once past the deadline
+    advance_ticks(5);
+    assert_eq!(token.progress(), Some(1.0));
+
+    // a reset starts over while the creation time is kept
+    token.reset();
+    assert_eq!(token.progress(), Some(0.0));
+    advance_ticks(5);
+    assert_eq!(token.progress(), Some(0.5));
+
+    let token = TimedOptionAged::<_, Ticks>::new("space_patato", 0);
+    assert_eq!(token.progress(), Some(1.0));
+
+    assert_eq!(TimedOptionAged::<&str, Ticks>::empty().progress(), None);
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn timed_option_aged_progress_sub_millisecond() {
+    use std::time::Duration;
+    use timed_option::MockClock;
+
+    MockClock::set_now(Duration::from_secs(100));
+    let token = TimedOptionAged::<_, MockClock>::new("space_patato", Duration::from_micros(900));
+    assert_eq!(token.progress(), Some(0.0));
+
+    MockClock::advance(Duration::from_micros(450));
+    assert_eq!(token.progress(), Some(0.5));
+
+    let token = TimedOptionAged::<_, MockClock>::new("space_patato", Duration::from_micros(1500));
+    MockClock::advance(Duration::from_micros(999));
+    assert!(token.progress().is_some_and(|p| p > 0.66 && p < 0.67));
+
+    let token = TimedOptionAged::<_, MockClock>::new("space_patato", Duration::ZERO);
+    assert_eq!(token.progress(), Some(1.0));
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "quanta")]
+
+use std::time::Duration;
+
+use quanta::Instant;
+
+use timed_option::{TimedOption, TimedValue, TtlBackend};
+
+#[test]
+fn quanta_instant_backend() {
+    let ttl = Duration::from_millis(10);
+    let token = TimedOption::<_, Instant>::new("space_patato", ttl);
+
+    assert!(token.is_some());
+    assert!(token.remaining().unwrap() <= ttl);
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+
+    std::thread::sleep(ttl);
+
+    assert!(token.is_none());
+    assert_eq!(token.remaining(), None);
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+    assert!(TimedOption::<&str, Instant>::empty().is_none());
+}
+
+#[test]
+fn quanta_instant_remaining_and_elapsed_past() {
+    let ahead = Instant::now() + Duration::from_secs(3500);
+    assert!(ahead.remaining().unwrap() <= Duration::from_secs(3500));
+    assert_eq!(ahead.elapsed_past(), None);
+
+    let behind = Instant::now();
+    std::thread::sleep(Duration::from_millis(10));
+    assert_eq!(behind.remaining(), None);
+    assert!(behind.elapsed_past().unwrap() >= Duration::from_millis(10));
+}
@@ -0,0 +1,38 @@
+use timed_option::{set_relative_now, RelativeMillis, TimedOption, TimedValue, TtlBackend};
+
+// a single test since the counter is shared by the whole test binary
+#[test]
+fn relative_millis_backend() {
+    set_relative_now(1_000);
+    let token = TimedOption::<_, RelativeMillis>::new("space_patato", 500);
+    assert_eq!(*token.expires_at(), RelativeMillis(1_500));
+    assert_eq!(token.remaining(), Some(500));
+
+    set_relative_now(1_499);
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+
+    set_relative_now(1_500);
+    assert!(token.is_none());
+    assert_eq!(token.expires_at().elapsed_past(), Some(0));
+    assert!(TimedOption::<&str, RelativeMillis>::empty().is_none());
+
+    // the deadline wraps past u32::MAX and still compares correctly
+    set_relative_now(u32::MAX - 100);
+    let token = TimedOption::<_, RelativeMillis>::new("space_patato", 500);
+    assert_eq!(*token.expires_at(), RelativeMillis(399));
+    assert!(token.is_some());
+
+    set_relative_now(10);
+    assert_eq!(token.remaining(), Some(389));
+    assert!(token.is_some());
+
+    set_relative_now(399);
+    assert!(token.is_none());
+    set_relative_now(1_000);
+    assert_eq!(token.expires_at().elapsed_past(), Some(601));
+
+    // ttls are capped so the deadline stays ahead of now
+    let token = TimedOption::<_, RelativeMillis>::new("space_patato", u32::MAX);
+    assert_eq!(token.remaining(), Some(i32::MAX as u32));
+    assert!(TimedOption::<_, RelativeMillis>::checked_new("space_patato", u32::MAX).is_none());
+}
@@ -0,0 +1,276 @@
+#![cfg(all(feature = "serde", feature = "std"))]
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use timed_option::{TimedOption, TimedValue};
+
+const TTL: Duration = Duration::from_secs(3500);
+
+#[derive(Serialize, Deserialize)]
+struct Remaining {
+    #[serde(with = "timed_option::as_remaining")]
+    token: TimedOption<String, Instant>,
+}
+
+#[test]
+fn as_remaining_round_trip() {
+    let cache = Remaining {
+        token: TimedOption::new(String::from("space_patato"), TTL),
+    };
+    let json = serde_json::to_value(&cache).unwrap();
+    assert_eq!(json["token"]["value"], "space_patato");
+    let remaining_secs = json["token"]["remaining_secs"].as_f64().unwrap();
+    assert!(remaining_secs > 3499.0 && remaining_secs <= 3500.0);
+
+    let restored: Remaining = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        restored.token.as_timed_value(),
+        TimedValue::Valid(&String::from("space_patato"))
+    );
+    assert!(restored.token.remaining().unwrap() <= TTL);
+}
+
+#[test]
+fn as_remaining_expired() {
+    let cache = Remaining {
+        token: TimedOption::new_expired(String::from("space_patato")),
+    };
+    let json = serde_json::to_string(&cache).unwrap();
+    assert_eq!(
+        json,
+        r#"{"token":{"value":"space_patato","remaining_secs":0.0}}"#
+    );
+
+    let restored: Remaining = serde_json::from_str(&json).unwrap();
+    assert!(restored.token.is_expired());
+
+    let restored: Remaining =
+        serde_json::from_str(r#"{"token":{"value":null,"remaining_secs":10.0}}"#).unwrap();
+    assert!(restored.token.is_none());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn as_rfc3339_round_trip() {
+    use chrono::{DateTime, Utc};
+
+    #[derive(Serialize, Deserialize)]
+    struct Session {
+        #[serde(with = "timed_option::as_rfc3339")]
+        token: TimedOption<String, DateTime<Utc>>,
+    }
+
+    let json =
+        r#"{"token":{"value":"space_patato","expires_at":"2025-01-01T00:00:00.123456789Z"}}"#;
+    let session: Session = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        *session.token.expires_at(),
+        DateTime::parse_from_rfc3339("2025-01-01T00:00:00.123456789Z").unwrap()
+    );
+    assert!(session.token.is_expired());
+    assert_eq!(serde_json::to_string(&session).unwrap(), json);
+
+    let session = Session {
+        token: TimedOption::new(String::from("space_patato"), chrono::Duration::hours(1)),
+    };
+    let restored: Session =
+        serde_json::from_str(&serde_json::to_string(&session).unwrap()).unwrap();
+    assert_eq!(restored.token.expires_at(), session.token.expires_at());
+    assert!(restored.token.is_some());
+}
+
+#[test]
+fn skip_expired() {
+    use timed_option::EpochMillis;
+
+    #[derive(Serialize, Deserialize)]
+    struct Cache {
+        #[serde(with = "timed_option::skip_expired")]
+        entry: TimedOption<String, EpochMillis>,
+    }
+
+    let deadline = EpochMillis(u64::MAX);
+    let cache = Cache {
+        entry: TimedOption::with_deadline(String::from("space_patato"), deadline),
+    };
+    let json = serde_json::to_value(&cache).unwrap();
+    assert_eq!(json["entry"]["value"], "space_patato");
+    assert!(json["entry"]["remaining"].as_u64().unwrap() > 0);
+    let restored: Cache = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        restored.entry.as_timed_value(),
+        TimedValue::Valid(&String::from("space_patato"))
+    );
+
+    let cache = Cache {
+        entry: TimedOption::new_expired(String::from("space_patato")),
+    };
+    let json = serde_json::to_string(&cache).unwrap();
+    assert_eq!(json, r#"{"entry":null}"#);
+    let restored: Cache = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.entry.as_timed_value(), TimedValue::None);
+
+    let cache = Cache {
+        entry: TimedOption::empty(),
+    };
+    assert_eq!(serde_json::to_string(&cache).unwrap(), r#"{"entry":null}"#);
+}
+
+#[test]
+fn skip_expired_instant() {
+    #[derive(Serialize, Deserialize)]
+    struct Cache {
+        #[serde(with = "timed_option::skip_expired")]
+        entry: TimedOption<String, Instant>,
+    }
+
+    let cache = Cache {
+        entry: TimedOption::new(String::from("space_patato"), TTL),
+    };
+    let json = serde_json::to_value(&cache).unwrap();
+    assert_eq!(json["entry"]["value"], "space_patato");
+    let remaining = json["entry"]["remaining"].as_u64().unwrap();
+    assert!(remaining > 3_499_000 && remaining <= 3_500_000);
+    let restored: Cache = serde_json::from_value(json).unwrap();
+    assert!(restored.entry.is_some());
+    let remaining = restored.entry.remaining().unwrap();
+    assert!(remaining <= TTL && remaining > TTL - Duration::from_secs(1));
+
+    let cache = Cache {
+        entry: TimedOption::new_expired(String::from("space_patato")),
+    };
+    assert_eq!(serde_json::to_string(&cache).unwrap(), r#"{"entry":null}"#);
+}
+
+#[derive(Serialize, Deserialize)]
+struct RemainingMillis {
+    #[serde(with = "timed_option::as_remaining_millis")]
+    token: TimedOption<String, Instant>,
+}
+
+#[test]
+fn as_remaining_millis_round_trip() {
+    let cache = RemainingMillis {
+        token: TimedOption::new(String::from("space_patato"), TTL),
+    };
+    let json = serde_json::to_value(&cache).unwrap();
+    assert_eq!(json["token"]["value"], "space_patato");
+    let remaining = json["token"]["remaining"].as_u64().unwrap();
+    assert!(remaining > 3_499_000 && remaining <= 3_500_000);
+
+    let restored: RemainingMillis = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        restored.token.as_timed_value(),
+        TimedValue::Valid(&String::from("space_patato"))
+    );
+    let remaining = restored.token.remaining().unwrap();
+    assert!(remaining <= TTL && remaining > TTL - Duration::from_secs(1));
+}
+
+#[test]
+fn as_remaining_millis_expired() {
+    let cache = RemainingMillis {
+        token: TimedOption::new_expired(String::from("space_patato")),
+    };
+    let json = serde_json::to_string(&cache).unwrap();
+    assert_eq!(
+        json,
+        r#"{"token":{"value":"space_patato","remaining":null}}"#
+    );
+    let restored: RemainingMillis = serde_json::from_str(&json).unwrap();
+    assert!(restored.token.is_expired());
+
+    let cache = RemainingMillis {
+        token: TimedOption::empty(),
+    };
+    let json = serde_json::to_string(&cache).unwrap();
+    assert_eq!(json, r#"{"token":{"value":null,"remaining":null}}"#);
+    let restored: RemainingMillis = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.token.as_timed_value(), TimedValue::None);
+}
+
+#[test]
+fn as_remaining_millis_cleared() {
+    // a cleared value keeps its valid ttl, which is not written out
+    let mut token = TimedOption::new(String::from("space_patato"), TTL);
+    token.clear();
+    let cache = RemainingMillis { token };
+    let json = serde_json::to_string(&cache).unwrap();
+    assert_eq!(json, r#"{"token":{"value":null,"remaining":null}}"#);
+    let restored: RemainingMillis = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.token.as_timed_value(), TimedValue::None);
+    assert_eq!(restored.token.remaining(), None);
+}
+
+#[test]
+fn as_deadline_same_shape_across_backends() {
+    use std::time::SystemTime;
+
+    #[derive(Serialize, Deserialize)]
+    struct Cache {
+        #[serde(with = "timed_option::as_deadline")]
+        session: TimedOption<String, Instant>,
+        #[serde(with = "timed_option::as_deadline")]
+        token: TimedOption<String, SystemTime>,
+    }
+
+    let cache = Cache {
+        session: TimedOption::new(String::from("space_patato"), TTL),
+        token: TimedOption::new(String::from("space_patato"), TTL),
+    };
+    let json = serde_json::to_value(&cache).unwrap();
+    for entry in [&json["session"], &json["token"]] {
+        assert_eq!(entry["value"], "space_patato");
+        let secs = entry["deadline"]["Remaining"]["secs"].as_u64().unwrap();
+        assert!(secs == 3499 || secs == 3500);
+    }
+
+    let restored: Cache = serde_json::from_value(json).unwrap();
+    assert!(restored.session.is_some());
+    assert!(restored.token.remaining().unwrap() <= TTL);
+
+    let cache = Cache {
+        session: TimedOption::new_expired(String::from("space_patato")),
+        token: TimedOption::new_expired(String::from("space_patato")),
+    };
+    let json = serde_json::to_string(&cache).unwrap();
+    let expired = r#"{"value":"space_patato","deadline":"Expired"}"#;
+    assert_eq!(
+        json,
+        format!(r#"{{"session":{expired},"token":{expired}}}"#)
+    );
+    let restored: Cache = serde_json::from_str(&json).unwrap();
+    assert!(restored.session.is_expired());
+    assert!(restored.token.is_expired());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn as_deadline_chrono() {
+    use chrono::{DateTime, Utc};
+    use timed_option::SerializableDeadline;
+
+    #[derive(Serialize, Deserialize)]
+    struct Cache {
+        #[serde(with = "timed_option::as_deadline")]
+        token: TimedOption<String, DateTime<Utc>>,
+    }
+
+    let cache = Cache {
+        token: TimedOption::new(String::from("space_patato"), chrono::Duration::seconds(60)),
+    };
+    let json = serde_json::to_value(&cache).unwrap();
+    let secs = json["token"]["deadline"]["Remaining"]["secs"]
+        .as_u64()
+        .unwrap();
+    assert!(secs == 59 || secs == 60);
+    let restored: Cache = serde_json::from_value(json).unwrap();
+    assert!(restored.token.is_some());
+
+    assert_eq!(
+        SerializableDeadline::new(&DateTime::<Utc>::MIN_UTC),
+        SerializableDeadline::Expired
+    );
+}
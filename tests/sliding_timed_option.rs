@@ -0,0 +1,56 @@
+#![cfg(feature = "mock")]
+
+use std::time::Duration;
+
+use timed_option::{MockClock, SlidingTimedOption, TimedOption};
+
+#[test]
+fn sliding_timed_option_get_keeps_alive() {
+    MockClock::set_now(Duration::from_secs(100));
+    let mut token =
+        SlidingTimedOption::<_, MockClock>::new("space_patato", Duration::from_secs(10));
+
+    // every access within the window pushes the deadline forward
+    for _ in 0..5 {
+        MockClock::advance(Duration::from_secs(8));
+        assert_eq!(token.get(), Some(&"space_patato"));
+    }
+    assert_eq!(
+        *token.as_timed_option().expires_at(),
+        MockClock(Duration::from_secs(150))
+    );
+
+    // peeking does not slide the deadline
+    MockClock::advance(Duration::from_secs(8));
+    assert_eq!(token.as_option(), Some(&"space_patato"));
+    MockClock::advance(Duration::from_secs(2));
+    assert!(token.is_none());
+}
+
+#[test]
+fn sliding_timed_option_expired_not_resurrected() {
+    MockClock::set_now(Duration::from_secs(100));
+    let mut token =
+        SlidingTimedOption::<_, MockClock>::new("space_patato", Duration::from_secs(10));
+
+    MockClock::advance(Duration::from_secs(11));
+    assert_eq!(token.get(), None);
+    assert!(token.is_none());
+    assert_eq!(
+        *token.as_timed_option().expires_at(),
+        MockClock(Duration::from_secs(110))
+    );
+
+    token.set("hello_world");
+    assert_eq!(token.get(), Some(&"hello_world"));
+
+    let token: TimedOption<_, _> = token.into();
+    assert_eq!(*token.expires_at(), MockClock(Duration::from_secs(121)));
+}
+
+#[test]
+fn sliding_timed_option_empty() {
+    let mut token = SlidingTimedOption::<&str, MockClock>::empty(Duration::from_secs(10));
+    assert_eq!(token.get(), None);
+    assert_eq!(token.window(), &Duration::from_secs(10));
+}
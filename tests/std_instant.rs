@@ -1,6 +1,8 @@
+#![cfg(feature = "std")]
+
 use std::time::{Duration, Instant};
 
-use timed_option::{TimedOption, TimedValue};
+use timed_option::{TimedOption, TimedValue, TtlBackend};
 
 #[test]
 fn std_instant_backend() {
@@ -24,3 +26,48 @@ fn std_instant_backend() {
     assert!(!token.is_some());
     assert!(token.is_none());
 }
+
+#[test]
+fn std_instant_empty_is_expired() {
+    let empty = TimedOption::<&str, Instant>::empty();
+    assert!(*empty.expires_at() < Instant::now());
+    assert!(empty.is_none());
+
+    let token = TimedOption::<_, Instant>::new_expired("space_patato");
+    assert!(*token.expires_at() < Instant::now());
+    assert!(token.is_expired());
+}
+
+#[test]
+fn std_instant_zero_ttl() {
+    let token = TimedOption::<_, Instant>::new("space_patato", Duration::ZERO);
+    assert!(token.is_none());
+    assert!(token.is_expired());
+    assert_eq!(token.remaining(), None);
+
+    let deadline = Instant::now();
+    let token = TimedOption::with_deadline("space_patato", deadline);
+    assert!(token.is_expired());
+}
+
+#[test]
+fn std_instant_saturating_ttl() {
+    let token = TimedOption::<_, Instant>::new("forever", Duration::from_secs(u64::MAX));
+    assert!(token.is_some());
+    assert!(*token.expires_at() > Instant::now() + Duration::from_secs(1 << 32));
+
+    let mut token = TimedOption::<_, Instant>::new("forever", Duration::MAX);
+    token.extend(Duration::MAX);
+    assert!(token.is_some());
+}
+
+#[test]
+fn std_instant_remaining_and_elapsed_past() {
+    let ahead = Instant::now() + Duration::from_secs(3500);
+    assert!(ahead.remaining().unwrap() <= Duration::from_secs(3500));
+    assert_eq!(ahead.elapsed_past(), None);
+
+    let behind = Instant::now() - Duration::from_secs(1);
+    assert_eq!(behind.remaining(), None);
+    assert!(behind.elapsed_past().unwrap() >= Duration::from_secs(1));
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "std")]
+
+use std::time::{Duration, SystemTime};
+
+use timed_option::{TimedOption, TimedValue, TtlBackend};
+
+#[test]
+fn system_time_backend() {
+    let ttl = Duration::from_secs(3500);
+    let mut token = TimedOption::<_, SystemTime>::new("space_patato", ttl);
+
+    assert!(token.is_some());
+    assert!(token.remaining().unwrap() <= ttl);
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+
+    token.expire();
+
+    assert!(token.is_none());
+    assert_eq!(token.remaining(), None);
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+}
+
+#[test]
+fn system_time_saturating_ttl() {
+    let token = TimedOption::<_, SystemTime>::new("forever", Duration::MAX);
+    assert!(token.is_some());
+    assert!(*token.expires_at() > SystemTime::now() + Duration::from_secs(1 << 30));
+}
+
+#[test]
+fn system_time_remaining_and_elapsed_past() {
+    let ahead = SystemTime::now() + Duration::from_secs(3500);
+    assert!(ahead.remaining().unwrap() <= Duration::from_secs(3500));
+    assert_eq!(ahead.elapsed_past(), None);
+
+    let behind = SystemTime::now() - Duration::from_secs(1);
+    assert_eq!(behind.remaining(), None);
+    assert!(behind.elapsed_past().unwrap() >= Duration::from_secs(1));
+}
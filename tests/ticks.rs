@@ -0,0 +1,35 @@
+use timed_option::{advance_ticks, set_ticks, Ticks, TimedOption, TimedValue, TtlBackend};
+
+// a single test since the tick counter is shared by the whole test binary
+#[test]
+fn ticks_backend() {
+    let token = TimedOption::<_, Ticks>::new("space_patato", 10);
+    assert!(token.is_some());
+    assert_eq!(token.remaining(), Some(10));
+
+    advance_ticks(9);
+    assert_eq!(token.remaining(), Some(1));
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+
+    advance_ticks(1);
+    assert!(token.is_none());
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+    assert!(TimedOption::<&str, Ticks>::empty().is_none());
+
+    set_ticks(1_000);
+    let token = TimedOption::<_, Ticks>::new("space_patato", 10);
+    assert_eq!(*token.expires_at(), Ticks(1_010));
+
+    set_ticks(1_010);
+    assert!(token.is_none());
+
+    let deadline = Ticks(1_100);
+    assert_eq!(deadline.remaining(), Some(90));
+    assert_eq!(deadline.elapsed_past(), None);
+
+    assert_eq!(Ticks(0).remaining(), None);
+    assert_eq!(Ticks(0).elapsed_past(), Some(1_010));
+}
@@ -0,0 +1,988 @@
+#![cfg(feature = "std")]
+
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use timed_option::{
+    latest, soonest, sweep_expired, ByDeadline, TimedError, TimedOption, TimedState, TimedValue,
+};
+
+const TTL: Duration = Duration::from_secs(3500);
+
+#[test]
+fn timed_option_map() {
+    let token = TimedOption::<_, Instant>::new("42", TTL);
+    assert_eq!(token.map(|s| s.parse::<u32>()).into_option(), Some(Ok(42)));
+    assert_eq!(token.as_ref().map(|s| s.len()).into_option(), Some(2));
+
+    let mut token = token;
+    token.expire();
+    assert_eq!(
+        token.map(|s| s.parse::<u32>()).into_timed_value(),
+        TimedValue::Expired(Ok(42))
+    );
+
+    let empty = TimedOption::<&str, Instant>::empty();
+    assert_eq!(empty.map(|s| s.len()).into_timed_value(), TimedValue::None);
+}
+
+#[test]
+fn timed_option_and_then() {
+    let short = Duration::from_millis(10);
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+
+    let chained = token.and_then(|s| TimedOption::new(s.len(), short));
+    assert_eq!(chained.into_option(), Some(12));
+
+    // the ttl from the inner timed-option takes over
+    std::thread::sleep(short);
+    assert_eq!(chained.into_timed_value(), TimedValue::Expired(12));
+    assert!(token.is_some());
+
+    let mut token = token;
+    token.expire();
+    let chained = token.and_then(|_| -> TimedOption<usize, Instant> { unreachable!() });
+    assert_eq!(chained.into_timed_value(), TimedValue::None);
+}
+
+#[test]
+fn timed_option_filter() {
+    let token = TimedOption::<_, Instant>::new(7, TTL);
+    assert_eq!(token.filter(|v| *v > 5).into_option(), Some(7));
+    assert_eq!(
+        token.filter(|v| *v > 10).into_timed_value(),
+        TimedValue::None
+    );
+
+    let mut token = token;
+    token.expire();
+    let filtered = token.filter(|_| unreachable!("predicate ran on expired value"));
+    assert_eq!(filtered.into_timed_value(), TimedValue::Expired(7));
+
+    let empty = TimedOption::<u8, Instant>::empty();
+    let filtered = empty.filter(|_| unreachable!("predicate ran on empty value"));
+    assert_eq!(filtered.into_timed_value(), TimedValue::None);
+}
+
+#[test]
+fn timed_option_unwrap_or() {
+    let mut token = TimedOption::<_, Instant>::new(String::from("space_patato"), TTL);
+    assert_eq!(token.clone().unwrap_or(String::from("x")), "space_patato");
+    assert_eq!(
+        token.clone().unwrap_or_else(|| unreachable!()),
+        "space_patato"
+    );
+    assert_eq!(token.clone().unwrap_or_default(), "space_patato");
+
+    token.expire();
+    assert_eq!(token.clone().unwrap_or(String::from("x")), "x");
+    assert_eq!(token.clone().unwrap_or_else(|| String::from("y")), "y");
+    assert_eq!(token.unwrap_or_default(), "");
+}
+
+#[test]
+fn timed_option_get_or_insert() {
+    let mut token = TimedOption::<_, Instant>::empty();
+    assert_eq!(*token.get_or_insert(1, TTL), 1);
+    assert_eq!(*token.get_or_insert(2, TTL), 1);
+
+    *token.get_or_insert_with(TTL, || unreachable!()) += 1;
+    assert_eq!(token.into_option(), Some(2));
+
+    // an expired value is overwritten and gets a fresh ttl
+    token.expire();
+    assert_eq!(*token.get_or_insert_with(TTL, || 3), 3);
+    assert_eq!(token.into_timed_value(), TimedValue::Valid(3));
+}
+
+#[test]
+fn timed_option_replace() {
+    let mut token = TimedOption::<_, Instant>::new("old_token", TTL);
+    assert_eq!(token.replace("new_token", TTL), Some("old_token"));
+    assert_eq!(token.into_option(), Some("new_token"));
+
+    token.expire();
+    assert_eq!(token.replace("newer_token", TTL), None);
+    assert_eq!(token.into_option(), Some("newer_token"));
+
+    let mut empty = TimedOption::<_, Instant>::empty();
+    assert_eq!(empty.replace("token", TTL), None);
+    assert!(empty.is_some());
+}
+
+#[test]
+fn timed_option_refresh() {
+    let mut token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    token.expire();
+    assert!(!token.is_some());
+
+    token.refresh(TTL);
+    assert!(token.is_some());
+    assert_eq!(token.into_option(), Some("space_patato"));
+
+    let mut empty = TimedOption::<&str, Instant>::empty();
+    empty.refresh(TTL);
+    assert!(empty.is_none());
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn timed_option_extend() {
+    use timed_option::MockClock;
+
+    MockClock::set_now(Duration::from_secs(100));
+    let mut token = TimedOption::<_, MockClock>::new("space_patato", Duration::from_secs(10));
+    MockClock::advance(Duration::from_secs(10));
+    assert!(token.is_none());
+
+    // relative to the old deadline, not to now
+    token.extend(Duration::from_secs(5));
+    assert!(token.is_some());
+    assert_eq!(*token.expires_at(), MockClock(Duration::from_secs(115)));
+
+    let step = Duration::from_secs(50);
+    let mut token = TimedOption::<_, MockClock>::new("space_patato", Duration::ZERO);
+    token.extend(step);
+    token.extend(step);
+    assert_eq!(*token.expires_at(), MockClock(Duration::from_secs(210)));
+
+    MockClock::advance(Duration::from_secs(99));
+    assert!(token.is_some());
+    MockClock::advance(Duration::from_secs(1));
+    assert!(token.is_none());
+}
+
+#[test]
+fn timed_option_remaining() {
+    let mut token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    let remaining = token.remaining().unwrap();
+    assert!(remaining <= TTL && remaining > TTL - Duration::from_secs(1));
+
+    token.expire();
+    assert_eq!(token.remaining(), None);
+
+    let token = TimedOption::<_, Instant>::new("space_patato", Duration::ZERO);
+    assert_eq!(token.remaining(), None);
+
+    let empty = TimedOption::<&str, Instant>::empty();
+    assert_eq!(empty.remaining(), None);
+}
+
+#[test]
+fn timed_option_expires_at() {
+    let before = Instant::now();
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    assert!(*token.expires_at() >= before + TTL);
+    assert!(*token.expires_at() <= Instant::now() + TTL);
+
+    let empty = TimedOption::<&str, Instant>::empty();
+    assert!(*empty.expires_at() <= Instant::now());
+}
+
+#[test]
+fn timed_option_set() {
+    let mut token = TimedOption::<_, Instant>::new("old_token", TTL);
+    token.expire();
+    token.set("new_token", TTL);
+    assert!(token.is_some());
+    assert_eq!(token.into_option(), Some("new_token"));
+
+    let mut empty = TimedOption::<_, Instant>::empty();
+    empty.set("token", TTL);
+    assert!(empty.is_some());
+}
+
+#[test]
+fn timed_option_or() {
+    let primary = TimedOption::<_, Instant>::new("primary", TTL);
+    let secondary = TimedOption::<_, Instant>::new("secondary", TTL);
+    let mut expired = TimedOption::<_, Instant>::new("expired", TTL);
+    expired.expire();
+
+    assert_eq!(primary.or(secondary).into_option(), Some("primary"));
+    assert_eq!(expired.or(secondary).into_option(), Some("secondary"));
+    assert_eq!(
+        primary.or_else(|| unreachable!()).into_option(),
+        Some("primary")
+    );
+    assert_eq!(
+        expired.or_else(|| secondary).into_option(),
+        Some("secondary")
+    );
+
+    let mut stale = TimedOption::<_, Instant>::new("stale", TTL);
+    stale.expire();
+    assert_eq!(
+        expired.or(stale).into_timed_value(),
+        TimedValue::Expired("stale")
+    );
+}
+
+#[test]
+fn timed_option_xor() {
+    let left = TimedOption::<_, Instant>::new("left", TTL);
+    let right = TimedOption::<_, Instant>::new("right", TTL);
+    let mut expired = TimedOption::<_, Instant>::new("expired", TTL);
+    expired.expire();
+    let empty = TimedOption::<&str, Instant>::empty();
+
+    assert_eq!(left.xor(empty).into_option(), Some("left"));
+    assert_eq!(expired.xor(right).into_option(), Some("right"));
+    assert_eq!(left.xor(right).into_timed_value(), TimedValue::None);
+    assert_eq!(expired.xor(expired).into_timed_value(), TimedValue::None);
+    assert_eq!(expired.xor(empty).into_timed_value(), TimedValue::None);
+}
+
+#[test]
+fn timed_option_zip() {
+    let short = TimedOption::<_, Instant>::new("short", Duration::from_secs(10));
+    let long = TimedOption::<_, Instant>::new(42, TTL);
+
+    let pair = short.zip(long);
+    assert_eq!(pair.into_option(), Some(("short", 42)));
+    assert_eq!(pair.expires_at(), short.expires_at());
+    assert_eq!(long.zip(short).expires_at(), short.expires_at());
+
+    let mut expired = long;
+    expired.expire();
+    assert_eq!(short.zip(expired).into_timed_value(), TimedValue::None);
+}
+
+#[test]
+fn timed_option_contains() {
+    let mut token = TimedOption::<_, Instant>::new("abc", TTL);
+    assert!(token.contains(&"abc"));
+    assert!(!token.contains(&"xyz"));
+
+    token.expire();
+    assert!(!token.contains(&"abc"));
+
+    let empty = TimedOption::<&str, Instant>::empty();
+    assert!(!empty.contains(&"abc"));
+}
+
+#[test]
+fn timed_option_inspect() {
+    let mut hits = Vec::new();
+    let mut stale = Vec::new();
+
+    let token = TimedOption::<_, Instant>::new("hit", TTL)
+        .inspect(|v| hits.push(*v))
+        .inspect_expired(|v| stale.push(*v));
+    assert_eq!(token.into_option(), Some("hit"));
+
+    let mut token = TimedOption::<_, Instant>::new("stale", TTL);
+    token.expire();
+    let token = token
+        .inspect(|v| hits.push(*v))
+        .inspect_expired(|v| stale.push(*v));
+    assert_eq!(token.into_timed_value(), TimedValue::Expired("stale"));
+
+    TimedOption::<&str, Instant>::empty()
+        .inspect(|v| hits.push(*v))
+        .inspect_expired(|v| stale.push(*v));
+
+    assert_eq!(hits, ["hit"]);
+    assert_eq!(stale, ["stale"]);
+}
+
+#[test]
+fn timed_option_as_mut() {
+    let mut counter = TimedOption::<_, Instant>::new(0, TTL);
+    if let Some(count) = counter.as_mut() {
+        *count += 1;
+    }
+    assert_eq!(counter.into_option(), Some(1));
+
+    counter.expire();
+    assert_eq!(counter.as_mut(), None);
+    assert_eq!(counter.into_timed_value(), TimedValue::Expired(1));
+}
+
+#[test]
+fn timed_option_iter() {
+    let mut token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    assert_eq!(token.iter().collect::<Vec<_>>(), [&"space_patato"]);
+    for value in &token {
+        assert_eq!(*value, "space_patato");
+    }
+
+    let mut values = vec!["first"];
+    values.extend(token);
+    assert_eq!(values, ["first", "space_patato"]);
+
+    token.expire();
+    assert_eq!(token.iter().count(), 0);
+    assert_eq!((&token).into_iter().count(), 0);
+    assert_eq!(token.into_iter().count(), 0);
+}
+
+#[test]
+fn timed_option_ok_or() {
+    let mut token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    assert_eq!(token.ok_or("missing"), Ok("space_patato"));
+    assert_eq!(
+        token.ok_or_else(|| unreachable!()),
+        Ok::<_, ()>("space_patato")
+    );
+
+    token.expire();
+    assert_eq!(token.ok_or("missing"), Err("missing"));
+    assert_eq!(token.ok_or_else(|| "stale"), Err("stale"));
+
+    let empty = TimedOption::<&str, Instant>::empty();
+    assert_eq!(empty.ok_or("missing"), Err("missing"));
+}
+
+#[test]
+fn timed_option_parts() {
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    let (value, deadline) = token.into_parts();
+    assert_eq!(value, Some("space_patato"));
+    assert_eq!(deadline, *token.expires_at());
+
+    let restored = TimedOption::from_parts(value, deadline);
+    assert_eq!(restored.into_parts(), token.into_parts());
+
+    let mut expired = token;
+    expired.expire();
+    let (value, deadline) = expired.into_parts();
+    assert_eq!(value, Some("space_patato"));
+    assert_eq!(
+        TimedOption::from_parts(value, deadline).into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+}
+
+#[test]
+fn timed_option_default() {
+    #[derive(Default)]
+    struct Config {
+        token: TimedOption<String, Instant>,
+    }
+
+    assert!(TimedOption::<String, Instant>::default().is_none());
+    assert!(Config::default().token.is_none());
+}
+
+#[test]
+fn timed_option_eq() {
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    let mut expired = token;
+    expired.expire();
+
+    assert_eq!(
+        token,
+        TimedOption::new("space_patato", Duration::from_secs(10))
+    );
+    assert_ne!(token, TimedOption::new("space_tomato", TTL));
+    assert_ne!(token, expired);
+
+    let mut other_expired = TimedOption::new("space_patato", TTL);
+    other_expired.expire();
+    assert_eq!(expired, other_expired);
+    assert_ne!(expired, TimedOption::empty());
+
+    assert_eq!(TimedOption::<&str, Instant>::empty(), TimedOption::empty());
+    let mut cleared = token;
+    cleared.clear();
+    assert_eq!(cleared, TimedOption::empty());
+}
+
+#[test]
+fn timed_option_new_expired() {
+    let stale = TimedOption::<_, Instant>::new_expired(5);
+    assert!(stale.is_none());
+    assert_eq!(stale.into_timed_value(), TimedValue::Expired(5));
+}
+
+#[test]
+fn timed_option_with_deadline() {
+    let deadline = Instant::now() + TTL;
+    let token = TimedOption::with_deadline("space_patato", deadline);
+    assert_eq!(*token.expires_at(), deadline);
+    assert!(token.is_some());
+
+    let past = Instant::now() - Duration::from_millis(1);
+    let token = TimedOption::with_deadline("space_patato", past);
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+}
+
+#[test]
+fn timed_option_take_if() {
+    let mut token = TimedOption::<_, Instant>::new(1, TTL);
+    assert_eq!(token.take_if(|v| *v > 1), None);
+    assert_eq!(token.take_if(|v| *v == 1), Some(1));
+    assert_eq!(token.into_timed_value(), TimedValue::None);
+
+    let mut token = TimedOption::<_, Instant>::new_expired(1);
+    assert_eq!(token.take_if(|_| unreachable!()), None);
+    assert_eq!(token.into_timed_value(), TimedValue::Expired(1));
+
+    let mut empty = TimedOption::<u8, Instant>::empty();
+    assert_eq!(empty.take_if(|_| unreachable!()), None);
+}
+
+#[test]
+fn timed_option_is_expired() {
+    assert!(!TimedOption::<u8, Instant>::empty().is_expired());
+    assert!(TimedOption::<_, Instant>::new_expired(5).is_expired());
+    assert!(!TimedOption::<_, Instant>::new(5, TTL).is_expired());
+
+    assert!(TimedOption::<u8, Instant>::empty().is_none());
+    assert!(TimedOption::<_, Instant>::new_expired(5).is_none());
+}
+
+#[test]
+fn timed_option_display() {
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    assert_eq!(token.to_string(), "Some(space_patato)");
+    assert_eq!(
+        TimedOption::<_, Instant>::new_expired(5).to_string(),
+        "Stale(5)"
+    );
+    assert_eq!(TimedOption::<u8, Instant>::empty().to_string(), "None");
+}
+
+#[test]
+fn timed_option_transpose() {
+    let token = TimedOption::<Result<u8, &str>, Instant>::new(Ok(5), TTL);
+    let deadline = *token.expires_at();
+    let token = token.transpose().unwrap();
+    assert_eq!(token.into_timed_value(), TimedValue::Valid(5));
+    assert_eq!(*token.expires_at(), deadline);
+
+    let token = TimedOption::<Result<u8, &str>, Instant>::new(Err("space_patato"), TTL);
+    assert_eq!(token.transpose(), Err("space_patato"));
+
+    let token = TimedOption::<Result<u8, &str>, Instant>::new_expired(Ok(5));
+    assert_eq!(
+        token.transpose().unwrap().into_timed_value(),
+        TimedValue::None
+    );
+
+    // an expired error is still surfaced
+    let token = TimedOption::<Result<u8, &str>, Instant>::new_expired(Err("space_patato"));
+    assert_eq!(token.transpose(), Err("space_patato"));
+
+    let token = TimedOption::<Result<u8, &str>, Instant>::empty();
+    assert_eq!(
+        token.transpose().unwrap().into_timed_value(),
+        TimedValue::None
+    );
+}
+
+#[test]
+fn timed_option_flatten() {
+    let inner = TimedOption::<_, Instant>::new("space_patato", TTL);
+    let outer = TimedOption::<_, Instant>::new(inner, TTL * 2);
+    let token = outer.flatten();
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+    assert_eq!(*token.expires_at(), *inner.expires_at());
+
+    // an expired inner layer expires the result
+    let inner = TimedOption::<_, Instant>::new_expired("space_patato");
+    let outer = TimedOption::<_, Instant>::new(inner, TTL);
+    assert_eq!(
+        outer.flatten().into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+
+    let inner = TimedOption::<_, Instant>::new("space_patato", TTL);
+    let outer = TimedOption::<_, Instant>::new_expired(inner);
+    assert_eq!(
+        outer.flatten().into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+
+    let outer = TimedOption::<_, Instant>::new(TimedOption::<u8, Instant>::empty(), TTL);
+    assert_eq!(outer.flatten().into_timed_value(), TimedValue::None);
+    let outer = TimedOption::<TimedOption<u8, Instant>, Instant>::empty();
+    assert_eq!(outer.flatten().into_timed_value(), TimedValue::None);
+}
+
+#[test]
+fn timed_option_by_deadline() {
+    let late = TimedOption::<_, Instant>::new("late", TTL * 2);
+    let soon = TimedOption::<_, Instant>::new("soon", TTL);
+    let stale = TimedOption::<_, Instant>::new_expired("stale");
+
+    let mut tokens = [ByDeadline(late), ByDeadline(stale), ByDeadline(soon)];
+    tokens.sort();
+    let order: Vec<_> = tokens
+        .iter()
+        .map(|t| *t.0.as_timed_value().unwrap())
+        .collect();
+    assert_eq!(order, ["stale", "soon", "late"]);
+
+    let mut heap: BinaryHeap<_> = [late, soon].map(|t| Reverse(ByDeadline(t))).into();
+    assert_eq!(heap.pop().unwrap().0 .0.as_option(), Some(&"soon"));
+
+    // the values are ignored
+    let deadline = *soon.expires_at();
+    assert_eq!(
+        ByDeadline(TimedOption::<_, Instant>::with_deadline(1, deadline)),
+        ByDeadline(TimedOption::<_, Instant>::with_deadline(2, deadline))
+    );
+}
+
+#[test]
+fn timed_option_hash() {
+    let mut set = HashSet::new();
+    assert!(set.insert(TimedOption::<_, Instant>::new("space_patato", TTL)));
+    assert!(!set.insert(TimedOption::<_, Instant>::new("space_patato", TTL * 2)));
+    assert!(set.insert(TimedOption::<_, Instant>::new_expired("space_patato")));
+
+    // every empty timed-option is the same key
+    assert!(set.insert(TimedOption::<&str, Instant>::empty()));
+    let mut cleared = TimedOption::<_, Instant>::new("hello_world", TTL);
+    cleared.clear();
+    assert!(!set.insert(cleared));
+    assert_eq!(set.len(), 3);
+}
+
+#[test]
+fn timed_option_peek() {
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    assert_eq!(token.peek(), Some(&"space_patato"));
+
+    let mut token = TimedOption::<_, Instant>::new_expired(1);
+    assert_eq!(token.as_option(), None);
+    assert_eq!(token.peek(), Some(&1));
+    *token.peek_mut().unwrap() += 1;
+    assert_eq!(token.into_timed_value(), TimedValue::Expired(2));
+
+    let mut empty = TimedOption::<u8, Instant>::empty();
+    assert_eq!(empty.peek(), None);
+    assert_eq!(empty.peek_mut(), None);
+}
+
+#[test]
+fn timed_option_get_stale() {
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    assert_eq!(token.get_stale(), Some((&"space_patato", true)));
+
+    let token = TimedOption::<_, Instant>::new_expired("space_patato");
+    assert_eq!(token.get_stale(), Some((&"space_patato", false)));
+
+    assert_eq!(TimedOption::<u8, Instant>::empty().get_stale(), None);
+}
+
+#[test]
+fn timed_option_clear_expired() {
+    let mut token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    assert!(!token.clear_expired());
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+
+    let mut token = TimedOption::<_, Instant>::new_expired("space_patato");
+    assert!(token.clear_expired());
+    assert_eq!(token.into_timed_value(), TimedValue::None);
+    assert!(!token.clear_expired());
+
+    let mut empty = TimedOption::<u8, Instant>::empty();
+    assert!(!empty.clear_expired());
+}
+
+#[test]
+fn timed_option_sweep_expired() {
+    let mut map = HashMap::new();
+    map.insert("valid", TimedOption::<_, Instant>::new(1, TTL));
+    map.insert("expired", TimedOption::<_, Instant>::new_expired(2));
+    map.insert("empty", TimedOption::<_, Instant>::empty());
+    map.insert("also_valid", TimedOption::<_, Instant>::new(3, TTL));
+
+    assert_eq!(sweep_expired(&mut map), 2);
+    let mut keys: Vec<_> = map.keys().copied().collect();
+    keys.sort();
+    assert_eq!(keys, ["also_valid", "valid"]);
+    assert_eq!(sweep_expired(&mut map), 0);
+}
+
+#[test]
+fn timed_option_cloned() {
+    let token = TimedOption::<_, Instant>::new(String::from("space_patato"), TTL);
+    let cloned = token.as_ref().filter(|s| s.len() > 3).cloned();
+    assert_eq!(cloned.as_option(), Some(&String::from("space_patato")));
+    assert_eq!(*cloned.expires_at(), *token.expires_at());
+
+    let token = TimedOption::<_, Instant>::new_expired(5);
+    let copied = token.as_ref().copied();
+    assert_eq!(copied.into_timed_value(), TimedValue::Expired(5));
+    assert_eq!(*copied.expires_at(), *token.expires_at());
+}
+
+#[test]
+fn timed_option_merge() {
+    let soon = TimedOption::<_, Instant>::new("soon", TTL);
+    let late = TimedOption::<_, Instant>::new("late", TTL * 2);
+    assert_eq!(soon.merge(late).as_option(), Some(&"late"));
+    assert_eq!(late.merge(soon).as_option(), Some(&"late"));
+
+    // ties keep self
+    let deadline = *soon.expires_at();
+    let a = TimedOption::<_, Instant>::with_deadline("a", deadline);
+    let b = TimedOption::<_, Instant>::with_deadline("b", deadline);
+    assert_eq!(a.merge(b).as_option(), Some(&"a"));
+
+    // an expired value beats no value with an earlier deadline
+    let past = *TimedOption::<&str, Instant>::empty().expires_at();
+    let empty = TimedOption::<&str, _>::from_parts(None, past);
+    let stale = TimedOption::from_parts(Some("stale"), past + Duration::from_millis(1));
+    assert_eq!(
+        empty.merge(stale).into_timed_value(),
+        TimedValue::Expired("stale")
+    );
+    assert_eq!(
+        stale.merge(empty).into_timed_value(),
+        TimedValue::Expired("stale")
+    );
+
+    // but loses to a later valid one
+    assert_eq!(stale.merge(soon).as_option(), Some(&"soon"));
+
+    // a cleared option with the later deadline wins over an earlier value
+    let mut cleared = late;
+    cleared.clear();
+    assert_eq!(soon.merge(cleared).into_timed_value(), TimedValue::None);
+    assert_eq!(cleared.merge(soon).into_timed_value(), TimedValue::None);
+    assert_eq!(*soon.merge(cleared).expires_at(), *late.expires_at());
+}
+
+#[test]
+fn timed_option_soonest_and_latest() {
+    let tokens = [
+        TimedOption::<_, Instant>::new("mid", TTL * 2),
+        TimedOption::<_, Instant>::new("soon", TTL),
+        TimedOption::<_, Instant>::new("late", TTL * 3),
+    ];
+    assert_eq!(soonest(tokens).unwrap().as_option(), Some(&"soon"));
+    assert_eq!(latest(tokens).unwrap().as_option(), Some(&"late"));
+
+    // empty timed-options carry an expired deadline
+    let with_empty = [tokens[0], TimedOption::<_, Instant>::empty()];
+    assert_eq!(
+        soonest(with_empty).unwrap().into_timed_value(),
+        TimedValue::None
+    );
+
+    assert!(soonest(Vec::<TimedOption<u8, Instant>>::new()).is_none());
+    assert!(latest(Vec::<TimedOption<u8, Instant>>::new()).is_none());
+}
+
+#[test]
+fn timed_option_expect() {
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    assert_eq!(token.expect_valid("no patato"), "space_patato");
+    assert_eq!(token.expect_some("no patato"), "space_patato");
+
+    let token = TimedOption::<_, Instant>::new_expired("space_patato");
+    assert_eq!(token.expect_some("no patato"), "space_patato");
+}
+
+#[test]
+#[should_panic(expected = "no patato: value expired")]
+fn timed_option_expect_valid_expired() {
+    TimedOption::<_, Instant>::new_expired("space_patato").expect_valid("no patato");
+}
+
+#[test]
+#[should_panic(expected = "no patato: value absent")]
+fn timed_option_expect_valid_absent() {
+    TimedOption::<&str, Instant>::empty().expect_valid("no patato");
+}
+
+#[test]
+#[should_panic(expected = "no patato: value absent")]
+fn timed_option_expect_some_absent() {
+    TimedOption::<&str, Instant>::empty().expect_some("no patato");
+}
+
+#[test]
+fn timed_option_try_map() {
+    let token = TimedOption::<_, Instant>::new("42", TTL);
+    let parsed = token.try_map(|s| s.parse::<u32>()).unwrap();
+    assert_eq!(parsed.into_timed_value(), TimedValue::Valid(42));
+    assert_eq!(*parsed.expires_at(), *token.expires_at());
+
+    let token = TimedOption::<_, Instant>::new_expired("42");
+    let parsed = token.try_map(|s| s.parse::<u32>()).unwrap();
+    assert_eq!(parsed.into_timed_value(), TimedValue::Expired(42));
+
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    assert!(token.try_map(|s| s.parse::<u32>()).is_err());
+
+    let empty = TimedOption::<&str, Instant>::empty();
+    let mapped = empty.try_map(|_| -> Result<u32, ()> { unreachable!() });
+    assert_eq!(mapped.unwrap().into_timed_value(), TimedValue::None);
+}
+
+#[test]
+fn timed_option_as_timed_value_mut() {
+    let mut token = TimedOption::<_, Instant>::new_expired(String::from("record"));
+    if let TimedValue::Expired(record) = token.as_timed_value_mut() {
+        record.push_str(" (stale)");
+    }
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired(String::from("record (stale)"))
+    );
+
+    let mut token = TimedOption::<_, Instant>::new(1, TTL);
+    *token.as_timed_value_mut().unwrap_valid() += 1;
+    assert_eq!(token.into_timed_value(), TimedValue::Valid(2));
+
+    let mut empty = TimedOption::<u8, Instant>::empty();
+    assert_eq!(empty.as_timed_value_mut(), TimedValue::None);
+}
+
+#[test]
+fn timed_option_is_valid() {
+    let token = TimedOption::<_, Instant>::new(5, TTL);
+    assert!(token.is_valid());
+    assert!(token.deadline_is_valid());
+
+    let token = TimedOption::<_, Instant>::new_expired(5);
+    assert!(!token.is_valid());
+    assert!(!token.deadline_is_valid());
+
+    // the deadline can outlive the value
+    let mut token = TimedOption::<_, Instant>::new(5, TTL);
+    token.clear();
+    assert!(!token.is_valid());
+    assert!(token.deadline_is_valid());
+}
+
+#[test]
+fn timed_option_get_or_try_insert_with() {
+    let mut token = TimedOption::<u8, Instant>::empty();
+    assert_eq!(
+        token.get_or_try_insert_with(TTL, || Err("db down")),
+        Err("db down")
+    );
+    assert_eq!(token.into_timed_value(), TimedValue::None);
+
+    let mut token = TimedOption::<_, Instant>::new_expired(1);
+    let deadline = *token.expires_at();
+    assert_eq!(
+        token.get_or_try_insert_with(TTL, || Err("db down")),
+        Err("db down")
+    );
+    assert_eq!(token.into_timed_value(), TimedValue::Expired(1));
+    assert_eq!(*token.expires_at(), deadline);
+
+    *token
+        .get_or_try_insert_with(TTL, || Ok::<_, &str>(2))
+        .unwrap() += 1;
+    assert_eq!(token.into_timed_value(), TimedValue::Valid(3));
+    assert_eq!(
+        token.get_or_try_insert_with(TTL, || -> Result<_, &str> { unreachable!() }),
+        Ok(&mut 3)
+    );
+}
+
+#[test]
+fn timed_option_replace_if_expired() {
+    let mut token = TimedOption::<_, Instant>::new(1, TTL);
+    let deadline = *token.expires_at();
+    assert!(!token.replace_if_expired(2, TTL * 2));
+    assert_eq!(token.into_timed_value(), TimedValue::Valid(1));
+    assert_eq!(*token.expires_at(), deadline);
+
+    let mut token = TimedOption::<_, Instant>::new_expired(1);
+    assert!(token.replace_if_expired(2, TTL));
+    assert_eq!(token.into_timed_value(), TimedValue::Valid(2));
+
+    let mut empty = TimedOption::<_, Instant>::empty();
+    assert!(empty.replace_if_expired(3, TTL));
+    assert_eq!(empty.into_timed_value(), TimedValue::Valid(3));
+}
+
+#[test]
+fn timed_option_checked_new() {
+    let token = TimedOption::<_, Instant>::checked_new("space_patato", TTL).unwrap();
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+
+    assert!(TimedOption::<_, Instant>::checked_new("space_patato", Duration::MAX).is_none());
+}
+
+#[test]
+fn timed_option_state() {
+    let token = TimedOption::<_, Instant>::new(5, TTL);
+    assert_eq!(token.state(), TimedState::Fresh);
+    assert_eq!(
+        TimedOption::<_, Instant>::new_expired(5).state(),
+        TimedState::Stale
+    );
+    assert_eq!(
+        TimedOption::<u8, Instant>::empty().state(),
+        TimedState::Empty
+    );
+
+    let mut cleared = token;
+    cleared.clear();
+    assert_eq!(cleared.state(), TimedState::Empty);
+}
+
+#[test]
+fn timed_option_new_batch() {
+    let batch = TimedOption::<_, Instant>::new_batch(0..1_000, TTL);
+    assert_eq!(batch.len(), 1_000);
+    let deadline = *batch[0].expires_at();
+    assert!(batch.iter().all(|token| *token.expires_at() == deadline));
+    assert_eq!(batch[42].into_timed_value(), TimedValue::Valid(42));
+
+    assert!(TimedOption::<u8, Instant>::new_batch([], TTL).is_empty());
+}
+
+#[test]
+fn timed_option_take_expired() {
+    let fresh = TimedOption::<_, Instant>::new(1, TTL);
+    let stale = TimedOption::<_, Instant>::new_expired(2);
+
+    // take and take_expired never both yield the value
+    let (mut a, mut b) = (fresh, fresh);
+    assert_eq!((a.take(), b.take_expired()), (Some(1), None));
+    let (mut a, mut b) = (stale, stale);
+    assert_eq!((a.take(), b.take_expired()), (None, Some(2)));
+
+    let mut token = fresh;
+    assert_eq!(token.take_expired(), None);
+    assert_eq!(token.into_timed_value(), TimedValue::Valid(1));
+
+    let mut token = stale;
+    assert_eq!(token.take_expired(), Some(2));
+    assert_eq!(token.into_timed_value(), TimedValue::None);
+
+    let mut empty = TimedOption::<u8, Instant>::empty();
+    assert_eq!(empty.take_expired(), None);
+}
+
+#[test]
+fn timed_option_into_owned() {
+    fn detach(raw: &str) -> TimedOption<Cow<'static, str>, Instant> {
+        TimedOption::new(Cow::Borrowed(raw), TTL).into_owned()
+    }
+
+    let raw = String::from("space_patato");
+    let token = detach(&raw);
+    drop(raw);
+    assert!(matches!(token.as_option(), Some(Cow::Owned(s)) if s == "space_patato"));
+    assert!(token.is_some());
+
+    let token = TimedOption::<Cow<str>, Instant>::new_expired(Cow::Borrowed("space_patato"));
+    let deadline = *token.expires_at();
+    let token = token.into_owned();
+    assert_eq!(*token.expires_at(), deadline);
+    assert!(token.is_expired());
+}
+
+#[test]
+fn timed_option_into_result() {
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    assert_eq!(token.as_result(), Ok(&"space_patato"));
+    assert_eq!(token.into_result(), Ok("space_patato"));
+
+    let token = TimedOption::<_, Instant>::new_expired("space_patato");
+    assert_eq!(token.as_result(), Err(TimedError::Expired));
+    assert_eq!(token.into_result(), Err(TimedError::Expired));
+
+    let token = TimedOption::<&str, Instant>::empty();
+    assert_eq!(token.into_result(), Err(TimedError::Absent));
+
+    fn lookup(token: TimedOption<u8, Instant>) -> Result<u8, Box<dyn std::error::Error>> {
+        Ok(token.into_result()? + 1)
+    }
+    assert_eq!(lookup(TimedOption::new(1, TTL)).unwrap(), 2);
+    let err = lookup(TimedOption::new_expired(1)).unwrap_err();
+    assert_eq!(err.to_string(), "value expired");
+    assert_eq!(TimedError::Absent.to_string(), "value absent");
+}
+
+#[test]
+fn timed_option_map_ttl() {
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    let deadline = *token.expires_at();
+    let token = token.map_ttl(|deadline| deadline + Duration::from_secs(60));
+    assert_eq!(*token.expires_at(), deadline + Duration::from_secs(60));
+    assert!(token.is_some());
+
+    // shortening the deadline expires the value
+    let token = token.map_ttl(|_| Instant::now());
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+}
+
+#[test]
+fn timed_option_eq_timed_value() {
+    let token = TimedOption::<_, Instant>::new("space_patato", TTL);
+    assert_eq!(token, TimedValue::Valid("space_patato"));
+    assert_ne!(token, TimedValue::Expired("space_patato"));
+    assert_ne!(token, TimedValue::Valid("hello_world"));
+
+    let token = TimedOption::<_, Instant>::new_expired("space_patato");
+    assert_eq!(token, TimedValue::Expired("space_patato"));
+    assert_ne!(token, TimedValue::Valid("space_patato"));
+
+    let token = TimedOption::<&str, Instant>::empty();
+    assert_eq!(token, TimedValue::None);
+    assert_ne!(token, TimedValue::Expired("space_patato"));
+}
+
+#[test]
+fn timed_option_and() {
+    let gate = TimedOption::<_, Instant>::new("session", TTL);
+    let token = TimedOption::<_, Instant>::new(5, TTL);
+    assert_eq!(gate.and(token), TimedValue::Valid(5));
+
+    // other is returned as is, even if expired
+    let stale = TimedOption::<_, Instant>::new_expired(5);
+    assert_eq!(gate.and(stale), TimedValue::Expired(5));
+
+    let gate = TimedOption::<_, Instant>::new_expired("session");
+    assert_eq!(gate.and(token), TimedValue::None);
+    let gate = TimedOption::<&str, Instant>::empty();
+    assert_eq!(gate.and(token), TimedValue::None);
+}
+
+#[test]
+fn timed_option_get_or_insert_tracked() {
+    let mut token = TimedOption::<_, Instant>::new(1, TTL);
+    let (value, state) = token.get_or_insert_tracked(TTL, || unreachable!());
+    assert_eq!((*value, state), (1, TimedState::Fresh));
+
+    let mut token = TimedOption::<_, Instant>::new_expired(1);
+    let (value, state) = token.get_or_insert_tracked(TTL, || 2);
+    assert_eq!((*value, state), (2, TimedState::Stale));
+    assert_eq!(token, TimedValue::Valid(2));
+
+    let mut token = TimedOption::<_, Instant>::empty();
+    let (value, state) = token.get_or_insert_tracked(TTL, || 3);
+    *value += 1;
+    assert_eq!(state, TimedState::Empty);
+    assert_eq!(token, TimedValue::Valid(4));
+}
+
+#[test]
+fn timed_option_replace_value() {
+    let mut token = TimedOption::<_, Instant>::new(1, TTL);
+    let deadline = *token.expires_at();
+    assert_eq!(token.replace_value(2), Some(1));
+    assert_eq!(*token.expires_at(), deadline);
+    assert_eq!(token, TimedValue::Valid(2));
+
+    // the new value inherits the expired deadline
+    let mut token = TimedOption::<_, Instant>::new_expired(1);
+    assert_eq!(token.replace_value(2), Some(1));
+    assert_eq!(token, TimedValue::Expired(2));
+
+    let mut token = TimedOption::<_, Instant>::empty();
+    assert_eq!(token.replace_value(3), None);
+    assert_eq!(token, TimedValue::Expired(3));
+}
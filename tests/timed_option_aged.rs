@@ -0,0 +1,70 @@
+#![cfg(feature = "mock")]
+
+use std::time::Duration;
+
+use timed_option::{MockClock, TimedOption, TimedOptionAged, TimedValue};
+
+#[test]
+fn timed_option_aged_age() {
+    MockClock::set_now(Duration::from_secs(100));
+    let token = TimedOptionAged::<_, MockClock>::new("space_patato", Duration::from_secs(10));
+    assert_eq!(
+        token.created_at(),
+        Some(&MockClock(Duration::from_secs(100)))
+    );
+    assert_eq!(token.age(), Some(Duration::ZERO));
+
+    MockClock::advance(Duration::from_secs(4));
+    assert_eq!(token.age(), Some(Duration::from_secs(4)));
+    assert_eq!(token.as_timed_value(), TimedValue::Valid(&"space_patato"));
+
+    // the age keeps growing after the value expired
+    MockClock::advance(Duration::from_secs(20));
+    assert!(token.is_none());
+    assert_eq!(token.age(), Some(Duration::from_secs(24)));
+}
+
+#[test]
+fn timed_option_aged_empty() {
+    let token = TimedOptionAged::<&str, MockClock>::empty();
+    assert!(token.is_none());
+    assert_eq!(token.created_at(), None);
+    assert_eq!(token.age(), None);
+}
+
+#[test]
+fn timed_option_aged_set() {
+    MockClock::set_now(Duration::from_secs(100));
+    let mut token = TimedOptionAged::<_, MockClock>::new("space_patato", Duration::from_secs(10));
+
+    MockClock::advance(Duration::from_secs(5));
+    token.set("hello_world", Duration::from_secs(10));
+    assert_eq!(token.age(), Some(Duration::ZERO));
+
+    let token: TimedOption<_, _> = token.into();
+    assert_eq!(*token.expires_at(), MockClock(Duration::from_secs(115)));
+}
+
+#[test]
+fn timed_option_aged_reset() {
+    MockClock::set_now(Duration::from_secs(100));
+    let mut token = TimedOptionAged::<_, MockClock>::new("space_patato", Duration::from_secs(10));
+    assert_eq!(token.lifetime(), Some(&Duration::from_secs(10)));
+
+    MockClock::advance(Duration::from_secs(15));
+    assert!(token.is_none());
+
+    // resurrects the value with its original lifetime
+    token.reset();
+    assert_eq!(token.as_timed_value(), TimedValue::Valid(&"space_patato"));
+    assert_eq!(
+        *token.as_timed_option().expires_at(),
+        MockClock(Duration::from_secs(125))
+    );
+    assert_eq!(token.age(), Some(Duration::from_secs(15)));
+
+    let mut token = TimedOptionAged::<&str, MockClock>::empty();
+    token.reset();
+    assert!(token.is_none());
+    assert_eq!(token.lifetime(), None);
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "mock")]
+
+use std::time::Duration;
+
+use timed_option::{MockClock, TimedOption, TimedOptionBuilder, TimedValue};
+
+const TTL: Duration = Duration::from_secs(60);
+
+#[test]
+fn timed_option_builder_ttl() {
+    MockClock::set_now(Duration::from_secs(100));
+    let token = TimedOption::<_, MockClock>::builder()
+        .value("space_patato")
+        .ttl(TTL)
+        .build();
+    assert_eq!(*token.expires_at(), MockClock(Duration::from_secs(160)));
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+}
+
+#[test]
+fn timed_option_builder_deadline() {
+    MockClock::set_now(Duration::from_secs(100));
+    let deadline = MockClock(Duration::from_secs(90));
+    let token = TimedOptionBuilder::new()
+        .value("space_patato")
+        .deadline(deadline)
+        .build();
+    assert_eq!(*token.expires_at(), deadline);
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+}
+
+#[test]
+fn timed_option_builder_defaults() {
+    let token = TimedOptionBuilder::<&str, MockClock>::new()
+        .ttl(TTL)
+        .build();
+    assert_eq!(token.into_timed_value(), TimedValue::None);
+
+    // a value without a ttl is expired
+    let token = TimedOptionBuilder::<_, MockClock>::default()
+        .value("space_patato")
+        .build();
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+}
+
+#[test]
+fn timed_option_builder_sliding() {
+    MockClock::set_now(Duration::from_secs(100));
+    let mut token = TimedOptionBuilder::<_, MockClock>::new()
+        .value("space_patato")
+        .sliding(TTL)
+        .build_sliding();
+    MockClock::advance(Duration::from_secs(50));
+    assert_eq!(token.get(), Some(&"space_patato"));
+    MockClock::advance(Duration::from_secs(50));
+    assert_eq!(token.get(), Some(&"space_patato"));
+    assert_eq!(token.window(), &TTL);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn timed_option_builder_jitter() {
+    MockClock::set_now(Duration::from_secs(100));
+    let deadlines: std::collections::HashSet<_> = (0..100)
+        .map(|_| {
+            TimedOptionBuilder::<_, MockClock>::new()
+                .value(())
+                .ttl(TTL)
+                .jitter(Duration::from_secs(10))
+                .build()
+        })
+        .map(|token| *token.expires_at())
+        .collect();
+    assert!(deadlines.len() > 1);
+    assert!(deadlines
+        .iter()
+        .all(|d| (Duration::from_secs(150)..=Duration::from_secs(170)).contains(&d.0)));
+}
+
+#[test]
+#[should_panic(expected = "both a ttl and a deadline are set")]
+fn timed_option_builder_ttl_and_deadline() {
+    TimedOptionBuilder::<_, MockClock>::new()
+        .value("space_patato")
+        .ttl(TTL)
+        .deadline(MockClock(Duration::from_secs(1)))
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "use `TimedOptionBuilder::build_sliding()`")]
+fn timed_option_builder_sliding_build() {
+    TimedOptionBuilder::<_, MockClock>::new()
+        .value("space_patato")
+        .sliding(TTL)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "a sliding timed-option only takes a window")]
+fn timed_option_builder_sliding_with_ttl() {
+    TimedOptionBuilder::<_, MockClock>::new()
+        .value("space_patato")
+        .ttl(TTL)
+        .sliding(TTL)
+        .build_sliding();
+}
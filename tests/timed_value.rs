@@ -58,3 +58,280 @@ fn timed_value_eq() {
         TimedValue::None
     );
 }
+
+#[test]
+fn timed_value_map() {
+    assert_eq!(TimedValue::Valid("42").map(str::len), TimedValue::Valid(2));
+    assert_eq!(
+        TimedValue::Expired("420").map(str::len),
+        TimedValue::Expired(3)
+    );
+    assert_eq!(TIMED_VALUE_NONE.map(|_| 0), TimedValue::None);
+}
+
+#[test]
+fn timed_value_unwrap() {
+    assert_eq!(TimedValue::Valid("Shook, shook").unwrap(), "Shook, shook");
+    assert_eq!(TimedValue::Expired("Took, took").unwrap(), "Took, took");
+    assert_eq!(
+        TimedValue::Expired("Took, took").expect("a value"),
+        "Took, took"
+    );
+    assert_eq!(
+        TimedValue::Valid("Shook, shook").unwrap_valid(),
+        "Shook, shook"
+    );
+}
+
+#[test]
+#[should_panic(expected = "called `TimedValue::unwrap()` on a `None` value")]
+fn timed_value_unwrap_none() {
+    TIMED_VALUE_NONE.unwrap();
+}
+
+#[test]
+#[should_panic(expected = "no song today")]
+fn timed_value_expect_none() {
+    TIMED_VALUE_NONE.expect("no song today");
+}
+
+#[test]
+#[should_panic(expected = "called `TimedValue::unwrap_valid()` on an `Expired` value")]
+fn timed_value_unwrap_valid_expired() {
+    TimedValue::Expired("Took, took").unwrap_valid();
+}
+
+#[test]
+#[should_panic(expected = "called `TimedValue::unwrap_valid()` on a `None` value")]
+fn timed_value_unwrap_valid_none() {
+    TIMED_VALUE_NONE.unwrap_valid();
+}
+
+#[test]
+fn timed_value_unwrap_or() {
+    assert_eq!(TimedValue::Valid("Seeing").unwrap_or("nothing"), "Seeing");
+    assert_eq!(TimedValue::Expired("purple").unwrap_or("nothing"), "purple");
+    assert_eq!(TimedValue::None.unwrap_or("nothing"), "nothing");
+
+    assert_eq!(
+        TimedValue::Expired("orange").unwrap_or_else(|| unreachable!()),
+        "orange"
+    );
+    assert_eq!(TimedValue::None.unwrap_or_else(|| "pink"), "pink");
+
+    assert_eq!(TimedValue::Expired(7).unwrap_or_default(), 7);
+    assert_eq!(TimedValue::<u8>::None.unwrap_or_default(), 0);
+}
+
+#[test]
+fn timed_value_into_option() {
+    assert_eq!(TimedValue::Valid("summer").into_option(), Some("summer"));
+    assert_eq!(TimedValue::Expired("summer").into_option(), None);
+    assert_eq!(TIMED_VALUE_NONE.into_option(), None);
+
+    assert_eq!(
+        TimedValue::Valid("summer").into_option_any(),
+        Some("summer")
+    );
+    assert_eq!(
+        TimedValue::Expired("summer").into_option_any(),
+        Some("summer")
+    );
+    assert_eq!(TIMED_VALUE_NONE.into_option_any(), None);
+
+    assert_eq!(TimedValue::Valid("dream").as_option(), Some(&"dream"));
+    assert_eq!(TimedValue::Expired("dream").as_option(), None);
+    assert_eq!(TIMED_VALUE_NONE.as_option(), None);
+
+    assert_eq!(TimedValue::Valid("dream").as_option_any(), Some(&"dream"));
+    assert_eq!(TimedValue::Expired("dream").as_option_any(), Some(&"dream"));
+    assert_eq!(TIMED_VALUE_NONE.as_option_any(), None);
+}
+
+#[test]
+fn timed_value_ok_or() {
+    assert_eq!(TimedValue::Valid("fresh").ok_or("nope"), Ok("fresh"));
+    assert_eq!(TimedValue::Expired("stale").ok_or("nope"), Err("nope"));
+    assert_eq!(TimedValue::<&str>::None.ok_or("nope"), Err("nope"));
+
+    assert_eq!(TimedValue::Valid("fresh").ok_or_stale("nope"), Ok("fresh"));
+    assert_eq!(
+        TimedValue::Expired("stale").ok_or_stale("nope"),
+        Ok("stale")
+    );
+    assert_eq!(TimedValue::<&str>::None.ok_or_stale("nope"), Err("nope"));
+}
+
+#[test]
+fn timed_value_and_then_filter() {
+    let parse = |s: &str| match s.parse::<u32>() {
+        Ok(n) => TimedValue::Valid(n),
+        Err(_) => TimedValue::None,
+    };
+    assert_eq!(
+        TimedValue::Valid("8118").and_then(parse),
+        TimedValue::Valid(8118)
+    );
+    assert_eq!(TimedValue::Valid("day").and_then(parse), TimedValue::None);
+    assert_eq!(
+        TimedValue::Expired("8118").and_then(|_| -> TimedValue<u32> { unreachable!() }),
+        TimedValue::None
+    );
+    assert_eq!(
+        TIMED_VALUE_NONE.and_then(|_| -> TimedValue<u32> { unreachable!() }),
+        TimedValue::None
+    );
+
+    assert_eq!(
+        TimedValue::Valid(8).filter(|v| *v > 1),
+        TimedValue::Valid(8)
+    );
+    assert_eq!(TimedValue::Valid(8).filter(|v| *v > 10), TimedValue::None);
+    assert_eq!(
+        TimedValue::Expired(8).filter(|_| unreachable!()),
+        TimedValue::Expired(8)
+    );
+    assert_eq!(
+        TIMED_VALUE_NONE.filter(|_| unreachable!()),
+        TimedValue::None
+    );
+}
+
+#[test]
+fn timed_value_as_mut() {
+    let mut stale = TimedValue::Expired(1);
+    if let TimedValue::Expired(count) = stale.as_mut() {
+        *count += 1;
+    }
+    assert_eq!(stale, TimedValue::Expired(2));
+
+    let mut fresh = TimedValue::Valid(String::from("heart"));
+    if let TimedValue::Valid(s) = fresh.as_mut() {
+        s.push_str(" cracking");
+    }
+    assert_eq!(fresh, TimedValue::Valid(String::from("heart cracking")));
+
+    let mut none = TimedValue::<u8>::None;
+    assert_eq!(none.as_mut(), TimedValue::None);
+}
+
+#[test]
+fn timed_value_iter() {
+    let values = [
+        TimedValue::Valid("fresh"),
+        TimedValue::Expired("stale"),
+        TimedValue::None,
+    ];
+
+    assert_eq!(values[0].iter_valid().count(), 1);
+    assert_eq!(values[1].iter_valid().count(), 0);
+    assert_eq!(values[2].iter_valid().count(), 0);
+
+    assert_eq!(values[0].iter_any().count(), 1);
+    assert_eq!(values[1].iter_any().count(), 1);
+    assert_eq!(values[2].iter_any().count(), 0);
+
+    let fresh: Vec<_> = values.iter().flatten().collect();
+    assert_eq!(fresh, [&"fresh"]);
+    let any: Vec<_> = values.iter().flat_map(TimedValue::iter_any).collect();
+    assert_eq!(any, [&"fresh", &"stale"]);
+    let owned: Vec<_> = values.into_iter().flatten().collect();
+    assert_eq!(owned, ["fresh"]);
+}
+
+#[test]
+fn timed_value_from_option() {
+    assert_eq!(
+        TimedValue::from(Some("summer")),
+        TimedValue::Valid("summer")
+    );
+    assert_eq!(TimedValue::<&str>::from(None), TimedValue::None);
+
+    assert_eq!(Option::from(TimedValue::Valid("summer")), Some("summer"));
+    assert_eq!(Option::from(TimedValue::Expired("summer")), None::<&str>);
+    assert_eq!(Option::<&str>::from(TIMED_VALUE_NONE.map(|_| "")), None);
+
+    for value in [Some("summer"), None] {
+        assert_eq!(Option::from(TimedValue::from(value)), value);
+    }
+}
+
+#[test]
+fn timed_value_variant_accessors() {
+    assert_eq!(TimedValue::Valid("yellow").valid(), Some("yellow"));
+    assert_eq!(TimedValue::Expired("yellow").valid(), None);
+    assert_eq!(TIMED_VALUE_NONE.valid(), None);
+
+    assert_eq!(TimedValue::Valid("yellow").expired(), None);
+    assert_eq!(TimedValue::Expired("yellow").expired(), Some("yellow"));
+    assert_eq!(TIMED_VALUE_NONE.expired(), None);
+
+    assert_eq!(TimedValue::Valid("pink").valid_ref(), Some(&"pink"));
+    assert_eq!(TimedValue::Expired("pink").valid_ref(), None);
+    assert_eq!(TIMED_VALUE_NONE.valid_ref(), None);
+
+    assert_eq!(TimedValue::Valid("pink").expired_ref(), None);
+    assert_eq!(TimedValue::Expired("pink").expired_ref(), Some(&"pink"));
+    assert_eq!(TIMED_VALUE_NONE.expired_ref(), None);
+}
+
+#[test]
+fn timed_value_map_variant() {
+    assert_eq!(
+        TimedValue::Valid(1).map_valid(|v| v + 1),
+        TimedValue::Valid(2)
+    );
+    assert_eq!(
+        TimedValue::Expired(1).map_valid(|_| unreachable!()),
+        TimedValue::Expired(1)
+    );
+    assert_eq!(
+        TimedValue::<u8>::None.map_valid(|_| unreachable!()),
+        TimedValue::None
+    );
+
+    assert_eq!(
+        TimedValue::Expired(1).map_expired(|v| v + 1),
+        TimedValue::Expired(2)
+    );
+    assert_eq!(
+        TimedValue::Valid(1).map_expired(|_| unreachable!()),
+        TimedValue::Valid(1)
+    );
+    assert_eq!(
+        TimedValue::<u8>::None.map_expired(|_| unreachable!()),
+        TimedValue::None
+    );
+}
+
+#[test]
+fn timed_value_default() {
+    struct NotDefault;
+
+    assert_eq!(TimedValue::<u8>::default(), TimedValue::None);
+    assert!(TimedValue::<NotDefault>::default().is_none());
+
+    let mut value = TimedValue::Valid(5);
+    assert_eq!(std::mem::take(&mut value), TimedValue::Valid(5));
+    assert_eq!(value, TimedValue::None);
+}
+
+#[test]
+fn timed_value_as_deref() {
+    assert_eq!(
+        TimedValue::Valid(String::from("x")).as_deref(),
+        TimedValue::Valid("x")
+    );
+    assert_eq!(
+        TimedValue::Expired(String::from("x")).as_deref(),
+        TimedValue::Expired("x")
+    );
+    assert_eq!(TimedValue::<String>::None.as_deref(), TimedValue::None);
+}
+
+#[test]
+fn timed_value_display() {
+    assert_eq!(TimedValue::Valid("summer").to_string(), "valid: summer");
+    assert_eq!(TimedValue::Expired(8118).to_string(), "expired: 8118");
+    assert_eq!(TIMED_VALUE_NONE.map(|_| 0).to_string(), "none");
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "tokio")]
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use timed_option::{TimedOption, TimedValue, TtlBackend};
+
+#[tokio::test(start_paused = true)]
+async fn tokio_instant_backend() {
+    let ttl = Duration::from_secs(3500);
+    let token = TimedOption::<_, Instant>::new("space_patato", ttl);
+    assert!(token.is_some());
+
+    tokio::time::advance(ttl - Duration::from_secs(1)).await;
+    assert_eq!(token.remaining(), Some(Duration::from_secs(1)));
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+
+    tokio::time::advance(Duration::from_secs(1)).await;
+    assert!(token.is_none());
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn tokio_instant_empty_is_expired() {
+    let empty = TimedOption::<&str, Instant>::empty();
+    assert!(*empty.expires_at() < Instant::now());
+    assert!(empty.is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn tokio_instant_remaining_and_elapsed_past() {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    assert_eq!(deadline.remaining(), Some(Duration::from_secs(10)));
+    assert_eq!(deadline.elapsed_past(), None);
+
+    tokio::time::advance(Duration::from_secs(15)).await;
+    assert_eq!(deadline.remaining(), None);
+    assert_eq!(deadline.elapsed_past(), Some(Duration::from_secs(5)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn tokio_instant_expiry() {
+    let token = TimedOption::<_, Instant>::new("space_patato", Duration::from_secs(10));
+    token.expiry().await;
+    assert_eq!(Instant::now(), *token.expires_at());
+    assert!(token.is_none());
+
+    // already expired, completes without moving the clock
+    let now = Instant::now();
+    token.expiry().await;
+    TimedOption::<&str, Instant>::empty().expiry().await;
+    assert_eq!(Instant::now(), now);
+}
+
+#[tokio::test]
+async fn tokio_std_instant_expiry() {
+    let token =
+        TimedOption::<_, std::time::Instant>::new("space_patato", Duration::from_millis(50));
+    tokio::select! {
+        _ = token.expiry() => {}
+        _ = tokio::time::sleep(Duration::from_secs(10)) => panic!("expiry did not complete"),
+    }
+    assert!(token.is_none());
+}
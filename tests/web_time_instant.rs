@@ -0,0 +1,40 @@
+#![cfg(all(feature = "web-time", target_family = "wasm", target_os = "unknown"))]
+
+use std::time::Duration;
+
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_time::Instant;
+
+use timed_option::{TimedOption, TimedValue, TtlBackend};
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn web_time_instant_backend() {
+    let ttl = Duration::from_millis(10);
+    let token = TimedOption::<_, Instant>::new("space_patato", ttl);
+    assert!(token.is_some());
+    assert_eq!(token.into_timed_value(), TimedValue::Valid("space_patato"));
+
+    // there is no thread::sleep in the browser, spin until the ttl has passed
+    let deadline = *token.expires_at();
+    while Instant::now() <= deadline {}
+
+    assert!(token.is_none());
+    assert_eq!(
+        token.into_timed_value(),
+        TimedValue::Expired("space_patato")
+    );
+}
+
+#[wasm_bindgen_test]
+fn web_time_instant_remaining_and_elapsed_past() {
+    let ahead = Instant::now() + Duration::from_secs(3500);
+    assert!(ahead.remaining().unwrap() <= Duration::from_secs(3500));
+    assert_eq!(ahead.elapsed_past(), None);
+
+    let behind = Instant::now();
+    while Instant::now() <= behind {}
+    assert_eq!(behind.remaining(), None);
+    assert!(behind.elapsed_past().is_some());
+}